@@ -1,11 +1,10 @@
 use super::{AppInfo, GenerateResults, Result};
 use crate as gen;
-use rrgen::RRgen;
 use serde_json::json;
 use std::path::Path;
 
 pub fn generate(
-    rrgen: &RRgen,
+    renderer: &gen::Renderer<'_>,
     name: &str,
     actions: &[String],
     kind: &gen::ScaffoldKind,
@@ -13,13 +12,16 @@ pub fn generate(
 ) -> Result<GenerateResults> {
     let vars = json!({"name": name, "actions": actions, "pkg_name": appinfo.app_name});
     match kind {
-        gen::ScaffoldKind::Api => gen::render_template(rrgen, Path::new("controller/api"), &vars),
+        gen::ScaffoldKind::Api => {
+            gen::render_template(renderer, Path::new("controller/api"), &vars)
+        }
         gen::ScaffoldKind::Html => {
             let mut gen_result =
-                gen::render_template(rrgen, Path::new("controller/html/controller.t"), &vars)?;
+                gen::render_template(renderer, Path::new("controller/html/controller.t"), &vars)?;
             for action in actions {
                 let vars = json!({"name": name, "action": action, "pkg_name": appinfo.app_name});
-                let res = gen::render_template(rrgen, Path::new("controller/html/view.t"), &vars)?;
+                let res =
+                    gen::render_template(renderer, Path::new("controller/html/view.t"), &vars)?;
                 gen_result.rrgen.extend(res.rrgen);
                 gen_result.local_templates.extend(res.local_templates);
             }
@@ -27,10 +29,11 @@ pub fn generate(
         }
         gen::ScaffoldKind::Htmx => {
             let mut gen_result =
-                gen::render_template(rrgen, Path::new("controller/htmx/controller.t"), &vars)?;
+                gen::render_template(renderer, Path::new("controller/htmx/controller.t"), &vars)?;
             for action in actions {
                 let vars = json!({"name": name, "action": action, "pkg_name": appinfo.app_name});
-                let res = gen::render_template(rrgen, Path::new("controller/htmx/view.t"), &vars)?;
+                let res =
+                    gen::render_template(renderer, Path::new("controller/htmx/view.t"), &vars)?;
                 gen_result.rrgen.extend(res.rrgen);
                 gen_result.local_templates.extend(res.local_templates);
             }
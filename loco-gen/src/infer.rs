@@ -1,6 +1,6 @@
-use cruet::{case::snake::to_snake_case, Inflector};
+use cruet::case::snake::to_snake_case;
 
-use crate::{Error, Result};
+use crate::{inflection::Inflections, Error, Result};
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum MigrationType {
@@ -21,6 +21,16 @@ pub enum FieldType {
     TypeWithParameters(String, Vec<String>),
 }
 
+/// splits a trailing default value off a field type:
+/// `string!:default:active` is (`string!`, `Some("active")`)
+#[must_use]
+pub fn split_default(ftype: &str) -> (&str, Option<&str>) {
+    match ftype.split_once(":default:") {
+        Some((ftype, value)) => (ftype, Some(value)),
+        None => (ftype, None),
+    }
+}
+
 pub fn parse_field_type(ftype: &str) -> Result<FieldType> {
     let parts: Vec<&str> = ftype.split(':').collect();
 
@@ -39,22 +49,22 @@ pub fn parse_field_type(ftype: &str) -> Result<FieldType> {
         [] => Err(Error::Message(format!("cannot parse type: `{ftype}`"))),
     }
 }
-pub fn guess_migration_type(migration_name: &str) -> MigrationType {
+pub fn guess_migration_type(migration_name: &str, inflections: &Inflections) -> MigrationType {
     let normalized_name = to_snake_case(migration_name);
     let parts: Vec<&str> = normalized_name.split('_').collect();
 
     match parts.as_slice() {
         ["create", table_name] => MigrationType::CreateTable {
-            table: table_name.to_plural(),
+            table: inflections.pluralize(table_name),
         },
         ["add", _reference_name, "ref", "to", table_name] => MigrationType::AddReference {
-            table: table_name.to_plural(),
+            table: inflections.pluralize(table_name),
         },
         ["add", _column_names @ .., "to", table_name] => MigrationType::AddColumns {
-            table: table_name.to_plural(),
+            table: inflections.pluralize(table_name),
         },
         ["remove", _column_names @ .., "from", table_name] => MigrationType::RemoveColumns {
-            table: table_name.to_plural(),
+            table: inflections.pluralize(table_name),
         },
         ["create", "join", "table", parts @ ..] => parts
             .iter()
@@ -70,8 +80,8 @@ pub fn guess_migration_type(migration_name: &str) -> MigrationType {
                 let table_a = first_parts.join("_");
                 let table_b = second_parts.join("_");
 
-                let table_a = table_a.to_singular();
-                let table_b = table_b.to_singular();
+                let table_a = inflections.singularize(&table_a);
+                let table_b = inflections.singularize(&table_b);
                 MigrationType::CreateJoinTable { table_a, table_b }
             }),
         _ => MigrationType::Empty,
@@ -85,7 +95,7 @@ mod tests {
     #[test]
     fn test_infer_create_table() {
         assert_eq!(
-            guess_migration_type("CreateUsers"),
+            guess_migration_type("CreateUsers", &Inflections::default()),
             MigrationType::CreateTable {
                 table: "users".to_string(),
             }
@@ -95,7 +105,7 @@ mod tests {
     #[test]
     fn test_infer_add_columns() {
         assert_eq!(
-            guess_migration_type("AddNameAndAgeToUsers"),
+            guess_migration_type("AddNameAndAgeToUsers", &Inflections::default()),
             MigrationType::AddColumns {
                 table: "users".to_string(),
             }
@@ -105,7 +115,7 @@ mod tests {
     #[test]
     fn test_infer_remove_columns() {
         assert_eq!(
-            guess_migration_type("RemoveNameAndAgeFromUsers"),
+            guess_migration_type("RemoveNameAndAgeFromUsers", &Inflections::default()),
             MigrationType::RemoveColumns {
                 table: "users".to_string(),
             }
@@ -115,7 +125,7 @@ mod tests {
     #[test]
     fn test_infer_add_reference() {
         assert_eq!(
-            guess_migration_type("AddUserRefToPosts"),
+            guess_migration_type("AddUserRefToPosts", &Inflections::default()),
             MigrationType::AddReference {
                 table: "posts".to_string(),
             }
@@ -125,7 +135,7 @@ mod tests {
     #[test]
     fn test_infer_create_join_table() {
         assert_eq!(
-            guess_migration_type("CreateJoinTableUsersAndGroups"),
+            guess_migration_type("CreateJoinTableUsersAndGroups", &Inflections::default()),
             MigrationType::CreateJoinTable {
                 table_a: "user".to_string(),
                 table_b: "group".to_string()
@@ -137,7 +147,10 @@ mod tests {
     fn test_infer_create_join_table_with_underscores() {
         // Test the specific case that was failing
         assert_eq!(
-            guess_migration_type("CreateJoinTableGlobal_recipesAndGlobal_materials"),
+            guess_migration_type(
+                "CreateJoinTableGlobal_recipesAndGlobal_materials",
+                &Inflections::default()
+            ),
             MigrationType::CreateJoinTable {
                 table_a: "global_recipe".to_string(),
                 table_b: "global_material".to_string()
@@ -149,7 +162,10 @@ mod tests {
     fn test_infer_create_join_table_complex_names() {
         // Test more complex table names with multiple underscores
         assert_eq!(
-            guess_migration_type("CreateJoinTableUser_profilesAndGroup_members"),
+            guess_migration_type(
+                "CreateJoinTableUser_profilesAndGroup_members",
+                &Inflections::default()
+            ),
             MigrationType::CreateJoinTable {
                 table_a: "user_profile".to_string(),
                 table_b: "group_member".to_string()
@@ -161,7 +177,10 @@ mod tests {
     fn test_infer_create_join_table_mixed_names() {
         // Test one simple name and one complex name
         assert_eq!(
-            guess_migration_type("CreateJoinTableUsersAndGroup_members"),
+            guess_migration_type(
+                "CreateJoinTableUsersAndGroup_members",
+                &Inflections::default()
+            ),
             MigrationType::CreateJoinTable {
                 table_a: "user".to_string(),
                 table_b: "group_member".to_string()
@@ -172,7 +191,7 @@ mod tests {
     #[test]
     fn test_empty_migration() {
         assert_eq!(
-            guess_migration_type("UnknownMigrationType"),
+            guess_migration_type("UnknownMigrationType", &Inflections::default()),
             MigrationType::Empty
         );
     }
@@ -181,7 +200,7 @@ mod tests {
     fn test_infer_create_join_table_no_and_separator() {
         // Test case where there's no "and" separator
         assert_eq!(
-            guess_migration_type("CreateJoinTableUsersGroups"),
+            guess_migration_type("CreateJoinTableUsersGroups", &Inflections::default()),
             MigrationType::Empty
         );
     }
@@ -190,7 +209,7 @@ mod tests {
     fn test_infer_create_join_table_empty_after_and() {
         // Test case where there are no parts after "and"
         assert_eq!(
-            guess_migration_type("CreateJoinTableUsersAnd"),
+            guess_migration_type("CreateJoinTableUsersAnd", &Inflections::default()),
             MigrationType::Empty
         );
     }
@@ -199,7 +218,7 @@ mod tests {
     fn test_infer_create_join_table_empty_before_and() {
         // Test case where there are no parts before "and"
         assert_eq!(
-            guess_migration_type("CreateJoinTableAndGroups"),
+            guess_migration_type("CreateJoinTableAndGroups", &Inflections::default()),
             MigrationType::Empty
         );
     }
@@ -208,7 +227,10 @@ mod tests {
     fn test_infer_create_join_table_multiple_ands() {
         // Test case with multiple "and" separators (should use first one)
         assert_eq!(
-            guess_migration_type("CreateJoinTableUsersAndGroupsAndMore"),
+            guess_migration_type(
+                "CreateJoinTableUsersAndGroupsAndMore",
+                &Inflections::default()
+            ),
             MigrationType::CreateJoinTable {
                 table_a: "user".to_string(),
                 table_b: "groups_and_more".to_string()
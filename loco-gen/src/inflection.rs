@@ -0,0 +1,186 @@
+//! Project-level overrides for the inflection rules used by the generators.
+//!
+//! Table names, migration file names and routes are derived by pluralizing the
+//! name given to a generator. The built-in rules come from `cruet` and can be
+//! wrong for domain-specific words, so a project may ship a
+//! [`DEFAULT_INFLECTIONS_FILE`] next to its `Cargo.toml`:
+//!
+//! ```yaml
+//! irregular:
+//!   person: persons
+//!   cactus: cacti
+//! uncountable:
+//!   - metadata
+//! ```
+//!
+//! Precedence is: `uncountable` first, then `irregular`, then the built-in
+//! rules. Only the last word of a compound name (`user_person`,
+//! `UserPerson`) is matched, case-insensitively.
+use std::{collections::BTreeMap, fs, path::Path};
+
+use cruet::string::{pluralize::to_plural, singularize::to_singular};
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Result};
+
+pub const DEFAULT_INFLECTIONS_FILE: &str = ".loco-inflections.yaml";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Inflections {
+    /// Irregular words, keyed by singular with the plural as value.
+    #[serde(default)]
+    pub irregular: BTreeMap<String, String>,
+    /// Words which are the same in singular and plural form.
+    #[serde(default)]
+    pub uncountable: Vec<String>,
+}
+
+impl Inflections {
+    /// Loads inflection overrides from a YAML file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        serde_yaml::from_str(&content).map_err(|err| {
+            Error::Message(format!(
+                "cannot parse inflections file `{}`: {err}",
+                path.display()
+            ))
+        })
+    }
+
+    /// Loads [`DEFAULT_INFLECTIONS_FILE`] from the current directory, or
+    /// returns empty overrides when the file does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load_local() -> Result<Self> {
+        let path = Path::new(DEFAULT_INFLECTIONS_FILE);
+        if path.exists() {
+            Self::from_path(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    #[must_use]
+    pub fn pluralize(&self, word: &str) -> String {
+        let (prefix, last) = split_last_word(word);
+        let lookup = last.to_lowercase();
+        if self.is_uncountable(&lookup) || self.irregular.values().any(|p| *p == lookup) {
+            return word.to_string();
+        }
+        match self.irregular.get(&lookup) {
+            Some(plural) => format!("{prefix}{}", match_case(last, plural)),
+            None => to_plural(word),
+        }
+    }
+
+    #[must_use]
+    pub fn singularize(&self, word: &str) -> String {
+        let (prefix, last) = split_last_word(word);
+        let lookup = last.to_lowercase();
+        if self.is_uncountable(&lookup) || self.irregular.contains_key(&lookup) {
+            return word.to_string();
+        }
+        match self.irregular.iter().find(|(_, plural)| **plural == lookup) {
+            Some((singular, _)) => format!("{prefix}{}", match_case(last, singular)),
+            None => to_singular(word),
+        }
+    }
+
+    fn is_uncountable(&self, word: &str) -> bool {
+        self.uncountable
+            .iter()
+            .any(|w| w.eq_ignore_ascii_case(word))
+    }
+}
+
+/// Splits a compound name into everything before its last word and the last
+/// word itself, for both `snake_case` and `PascalCase` names.
+fn split_last_word(word: &str) -> (&str, &str) {
+    let idx = word.rfind('_').map(|i| i + 1).or_else(|| {
+        word.char_indices()
+            .skip(1)
+            .filter(|(_, c)| c.is_uppercase())
+            .map(|(i, _)| i)
+            .last()
+    });
+    idx.map_or(("", word), |i| word.split_at(i))
+}
+
+fn match_case(original: &str, replacement: &str) -> String {
+    if original.starts_with(char::is_uppercase) {
+        let mut chars = replacement.chars();
+        chars.next().map_or_else(String::new, |first| {
+            first.to_uppercase().chain(chars).collect()
+        })
+    } else {
+        replacement.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inflections() -> Inflections {
+        Inflections {
+            irregular: BTreeMap::from([("person".to_string(), "persons".to_string())]),
+            uncountable: vec!["metadata".to_string()],
+        }
+    }
+
+    #[test]
+    fn can_pluralize_with_overrides() {
+        let inflections = inflections();
+        assert_eq!(inflections.pluralize("person"), "persons");
+        assert_eq!(inflections.pluralize("persons"), "persons");
+        assert_eq!(inflections.pluralize("user_person"), "user_persons");
+        assert_eq!(inflections.pluralize("UserPerson"), "UserPersons");
+        assert_eq!(inflections.pluralize("metadata"), "metadata");
+        assert_eq!(inflections.pluralize("movie"), "movies");
+    }
+
+    #[test]
+    fn can_singularize_with_overrides() {
+        let inflections = inflections();
+        assert_eq!(inflections.singularize("persons"), "person");
+        assert_eq!(inflections.singularize("user_persons"), "user_person");
+        assert_eq!(inflections.singularize("metadata"), "metadata");
+        assert_eq!(inflections.singularize("movies"), "movie");
+    }
+
+    #[test]
+    fn uncountable_takes_precedence_over_irregular() {
+        let mut inflections = inflections();
+        inflections.uncountable.push("person".to_string());
+        assert_eq!(inflections.pluralize("person"), "person");
+    }
+
+    #[test]
+    fn falls_back_to_builtin_rules() {
+        let inflections = Inflections::default();
+        assert_eq!(inflections.pluralize("person"), to_plural("person"));
+        assert_eq!(inflections.singularize("people"), to_singular("people"));
+    }
+
+    #[test]
+    fn can_load_from_file() {
+        let tree_fs = tree_fs::TreeBuilder::default()
+            .drop(true)
+            .add(
+                DEFAULT_INFLECTIONS_FILE,
+                "irregular:\n  person: persons\nuncountable:\n  - metadata\n",
+            )
+            .create()
+            .unwrap();
+
+        let inflections =
+            Inflections::from_path(&tree_fs.root.join(DEFAULT_INFLECTIONS_FILE)).unwrap();
+        assert_eq!(inflections, self::inflections());
+    }
+}
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 mod controller;
 use colored::Colorize;
+use heck::ToSnakeCase;
 use std::fmt::Write;
 use std::{
     collections::HashMap,
@@ -17,13 +18,19 @@ use std::{
 
 #[cfg(feature = "with-db")]
 mod infer;
+pub mod inflection;
+use inflection::Inflections;
 #[cfg(feature = "with-db")]
 mod migration;
 #[cfg(feature = "with-db")]
 mod model;
+mod preview;
+#[cfg(feature = "with-db")]
+pub use model::get_columns_and_references;
 #[cfg(feature = "with-db")]
 mod scaffold;
 pub mod template;
+use template::{FileSystemTemplateSource, TemplateSource};
 pub mod tera_ext;
 #[cfg(test)]
 mod testutil;
@@ -222,6 +229,32 @@ pub enum ScaffoldKind {
     Htmx,
 }
 
+impl ScaffoldKind {
+    /// Names accepted when parsing a kind, e.g. `api`.
+    #[must_use]
+    pub fn names() -> Vec<String> {
+        <Self as clap::ValueEnum>::value_variants()
+            .iter()
+            .filter_map(clap::ValueEnum::to_possible_value)
+            .map(|value| value.get_name().to_string())
+            .collect()
+    }
+}
+
+impl std::str::FromStr for ScaffoldKind {
+    type Err = Error;
+
+    /// Parses a kind by name, ignoring case.
+    fn from_str(s: &str) -> Result<Self> {
+        <Self as clap::ValueEnum>::from_str(s, true).map_err(|_| {
+            Error::Message(format!(
+                "unknown scaffold kind `{s}`, expected one of: {}",
+                Self::names().join(", ")
+            ))
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum DeploymentKind {
     Docker {
@@ -237,7 +270,7 @@ pub enum DeploymentKind {
     },
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Component {
     #[cfg(feature = "with-db")]
     Model {
@@ -274,6 +307,9 @@ pub enum Component {
 
         // k
         kind: ScaffoldKind,
+
+        /// Whether to wrap each controller action in a tracing span
+        with_tracing: bool,
     },
     Controller {
         /// Name of the thing to generate
@@ -311,6 +347,62 @@ pub struct AppInfo {
     pub app_name: String,
 }
 
+/// Settings for a single generation
+pub struct GenerateOptions {
+    /// Overrides of the built-in templates
+    pub templates: Box<dyn TemplateSource>,
+    /// Overrides of the built-in pluralization rules
+    pub inflections: Inflections,
+    /// Whether model generation migrates the database and regenerates the
+    /// entities afterwards, with `cargo loco-tool` in the current directory
+    pub run_migration: bool,
+}
+
+impl Default for GenerateOptions {
+    /// Overrides from [`template::DEFAULT_LOCAL_TEMPLATE`] in the current
+    /// directory, as `cargo loco generate` uses. Migrations run unless
+    /// `SKIP_MIGRATION` is set.
+    fn default() -> Self {
+        Self {
+            templates: Box::new(FileSystemTemplateSource::default()),
+            inflections: Inflections::default(),
+            run_migration: std::env::var("SKIP_MIGRATION").is_err(),
+        }
+    }
+}
+
+/// What the generators render with
+pub(crate) struct Renderer<'a> {
+    target: Target<'a>,
+    pub options: &'a GenerateOptions,
+}
+
+/// Where rendered files go
+enum Target<'a> {
+    Project(&'a RRgen),
+    /// Set by [`preview`], which collects the rendered files instead of
+    /// writing them
+    Preview(&'a preview::Preview),
+}
+
+impl Renderer<'_> {
+    pub fn is_preview(&self) -> bool {
+        matches!(self.target, Target::Preview(_))
+    }
+}
+
+/// A file rendered by [`preview`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedFile {
+    /// Built-in template it was rendered from, e.g. `model/model.t`
+    pub template: PathBuf,
+    /// Where generation writes it, relative to the project root
+    pub path: String,
+    pub content: String,
+    /// Existing files generation injects into, relative to the project root
+    pub injections: Vec<String>,
+}
+
 #[must_use]
 pub fn new_generator() -> RRgen {
     RRgen::default().add_template_engine(tera_ext::new())
@@ -322,6 +414,32 @@ pub fn new_generator() -> RRgen {
 ///
 /// This function will return an error if it fails
 pub fn generate(rrgen: &RRgen, component: Component, appinfo: &AppInfo) -> Result<GenerateResults> {
+    generate_with_options(rrgen, component, appinfo, &GenerateOptions::default())
+}
+
+/// Generate a component with the given [`GenerateOptions`]
+///
+/// # Errors
+///
+/// This function will return an error if it fails
+pub fn generate_with_options(
+    rrgen: &RRgen,
+    component: Component,
+    appinfo: &AppInfo,
+    options: &GenerateOptions,
+) -> Result<GenerateResults> {
+    let renderer = Renderer {
+        target: Target::Project(rrgen),
+        options,
+    };
+    generate_component(&renderer, component, appinfo)
+}
+
+fn generate_component(
+    renderer: &Renderer<'_>,
+    component: Component,
+    appinfo: &AppInfo,
+) -> Result<GenerateResults> {
     /*
     (1)
     XXX: remove hooks generic from child generator, materialize it here and pass it
@@ -335,40 +453,49 @@ pub fn generate(rrgen: &RRgen, component: Component, appinfo: &AppInfo) -> Resul
             name,
             with_tz,
             fields,
-        } => model::generate(rrgen, &name, with_tz, &fields, appinfo)?,
+        } => model::generate(renderer, &name, with_tz, &fields, appinfo)?,
         #[cfg(feature = "with-db")]
         Component::Scaffold {
             name,
             with_tz,
             fields,
             kind,
-        } => scaffold::generate(rrgen, &name, with_tz, &fields, &kind, appinfo)?,
+            with_tracing,
+        } => scaffold::generate(
+            renderer,
+            &name,
+            with_tz,
+            &fields,
+            &kind,
+            with_tracing,
+            appinfo,
+        )?,
         #[cfg(feature = "with-db")]
         Component::Migration {
             name,
             with_tz,
             fields,
-        } => migration::generate(rrgen, &name, with_tz, &fields, appinfo)?,
+        } => migration::generate(renderer, &name, with_tz, &fields, appinfo)?,
         Component::Controller {
             name,
             actions,
             kind,
-        } => controller::generate(rrgen, &name, &actions, &kind, appinfo)?,
+        } => controller::generate(renderer, &name, &actions, &kind, appinfo)?,
         Component::Task { name } => {
             let vars = json!({"name": name, "pkg_name": appinfo.app_name});
-            render_template(rrgen, Path::new("task"), &vars)?
+            render_template(renderer, Path::new("task"), &vars)?
         }
         Component::Scheduler {} => {
             let vars = json!({"pkg_name": appinfo.app_name});
-            render_template(rrgen, Path::new("scheduler"), &vars)?
+            render_template(renderer, Path::new("scheduler"), &vars)?
         }
         Component::Worker { name } => {
             let vars = json!({"name": name, "pkg_name": appinfo.app_name});
-            render_template(rrgen, Path::new("worker"), &vars)?
+            render_template(renderer, Path::new("worker"), &vars)?
         }
         Component::Mailer { name } => {
             let vars = json!({ "name": name });
-            render_template(rrgen, Path::new("mailer"), &vars)?
+            render_template(renderer, Path::new("mailer"), &vars)?
         }
         Component::Deployment { kind } => match kind {
             DeploymentKind::Docker {
@@ -380,7 +507,7 @@ pub fn generate(rrgen: &RRgen, component: Component, appinfo: &AppInfo) -> Resul
                     "copy_paths": copy_paths,
                     "is_client_side_rendering": is_client_side_rendering,
                 });
-                render_template(rrgen, Path::new("deployment/docker"), &vars)?
+                render_template(renderer, Path::new("deployment/docker"), &vars)?
             }
             DeploymentKind::Shuttle { runttime_version } => {
                 let vars = json!({
@@ -389,7 +516,7 @@ pub fn generate(rrgen: &RRgen, component: Component, appinfo: &AppInfo) -> Resul
                     "with_db": cfg!(feature = "with-db")
                 });
 
-                render_template(rrgen, Path::new("deployment/shuttle"), &vars)?
+                render_template(renderer, Path::new("deployment/shuttle"), &vars)?
             }
             DeploymentKind::Nginx { host, port } => {
                 let host = host.replace("http://", "").replace("https://", "");
@@ -398,39 +525,80 @@ pub fn generate(rrgen: &RRgen, component: Component, appinfo: &AppInfo) -> Resul
                     "domain": host,
                     "port": port
                 });
-                render_template(rrgen, Path::new("deployment/nginx"), &vars)?
+                render_template(renderer, Path::new("deployment/nginx"), &vars)?
             }
         },
         Component::Data { name } => {
             let vars = json!({ "name": name });
-            render_template(rrgen, Path::new("data"), &vars)?
+            render_template(renderer, Path::new("data"), &vars)?
         }
     };
 
     Ok(get_result)
 }
 
-fn render_template(rrgen: &RRgen, template: &Path, vars: &Value) -> Result<GenerateResults> {
+/// Adds `plural_name`, `plural_file_name` and `plural_table`, pluralized with
+/// the project's inflections. Templates use them instead of rrgen's `plural`
+/// filter, which only knows the built-in rules. `file_name` is the snake case
+/// `name` when the generator does not set it.
+fn with_plurals(vars: &Value, inflections: &Inflections) -> Value {
+    let mut vars = vars.clone();
+    if let Value::Object(map) = &mut vars {
+        let file_name = map
+            .get("file_name")
+            .and_then(Value::as_str)
+            .map(ToString::to_string)
+            .or_else(|| {
+                map.get("name")
+                    .and_then(Value::as_str)
+                    .map(ToSnakeCase::to_snake_case)
+            });
+        let words = [
+            (
+                "plural_name",
+                map.get("name")
+                    .and_then(Value::as_str)
+                    .map(ToString::to_string),
+            ),
+            ("plural_file_name", file_name),
+            (
+                "plural_table",
+                map.get("table")
+                    .and_then(Value::as_str)
+                    .map(ToString::to_string),
+            ),
+        ];
+        for (key, word) in words {
+            if let Some(word) = word {
+                map.insert(key.to_string(), Value::String(inflections.pluralize(&word)));
+            }
+        }
+    }
+    vars
+}
+
+fn render_template(
+    renderer: &Renderer<'_>,
+    template: &Path,
+    vars: &Value,
+) -> Result<GenerateResults> {
     let template_files = template::collect_files_from_path(template)?;
+    let vars = &with_plurals(vars, &renderer.options.inflections);
 
     let mut gen_result = vec![];
     let mut local_templates = vec![];
     for template in template_files {
-        let custom_template = Path::new(template::DEFAULT_LOCAL_TEMPLATE).join(template.path());
-
-        if custom_template.exists() {
-            let content = fs::read_to_string(&custom_template).map_err(|err| {
-                tracing::error!(custom_template = %custom_template.display(), "could not read custom template");
-                err
-            })?;
-            gen_result.push(rrgen.generate(&content, vars)?);
+        if let Some((custom_template, content)) =
+            renderer.options.templates.find(template.path())?
+        {
+            gen_result.push(render_file(renderer, template.path(), &content, vars)?);
             local_templates.push(custom_template);
         } else {
             let content = template.contents_utf8().ok_or(Error::Message(format!(
                 "could not get template content: {}",
                 template.path().display()
             )))?;
-            gen_result.push(rrgen.generate(content, vars)?);
+            gen_result.push(render_file(renderer, template.path(), content, vars)?);
         }
     }
 
@@ -440,6 +608,64 @@ fn render_template(rrgen: &RRgen, template: &Path, vars: &Value) -> Result<Gener
     })
 }
 
+fn render_file(
+    renderer: &Renderer<'_>,
+    template: &Path,
+    content: &str,
+    vars: &Value,
+) -> Result<GenResult> {
+    match &renderer.target {
+        Target::Project(rrgen) => Ok(rrgen.generate(content, vars)?),
+        Target::Preview(preview) => preview.render(template, content, vars),
+    }
+}
+
+/// Render the files generating `component` would create, without reading or
+/// writing the project. Model generation does not run the migrator.
+///
+/// # Errors
+///
+/// When the component's fields or templates are invalid.
+pub fn preview(
+    component: Component,
+    appinfo: &AppInfo,
+    options: &GenerateOptions,
+) -> Result<Vec<RenderedFile>> {
+    render_preview(None, component, appinfo, options)
+}
+
+/// Render the files generating `component` into the project at `root` would
+/// create, reading the project but not writing it. Files and injections the
+/// templates skip, e.g. with `skip_exists`, are left out.
+///
+/// # Errors
+///
+/// When the component's fields or templates are invalid, or a template
+/// injects into a file the project lacks.
+pub fn preview_in(
+    root: &Path,
+    component: Component,
+    appinfo: &AppInfo,
+    options: &GenerateOptions,
+) -> Result<Vec<RenderedFile>> {
+    render_preview(Some(root), component, appinfo, options)
+}
+
+fn render_preview(
+    root: Option<&Path>,
+    component: Component,
+    appinfo: &AppInfo,
+    options: &GenerateOptions,
+) -> Result<Vec<RenderedFile>> {
+    let preview = preview::Preview::new(root);
+    let renderer = Renderer {
+        target: Target::Preview(&preview),
+        options,
+    };
+    generate_component(&renderer, component, appinfo)?;
+    Ok(preview.into_files())
+}
+
 #[must_use]
 pub fn collect_messages(results: &GenerateResults) -> String {
     let mut messages = String::new();
@@ -748,4 +974,19 @@ The following templates were sourced from the local templates:
 "
         );
     }
+
+    #[test]
+    fn can_parse_scaffold_kind() {
+        assert!(matches!("api".parse(), Ok(ScaffoldKind::Api)));
+        assert!(matches!("HTML".parse(), Ok(ScaffoldKind::Html)));
+        assert!(matches!("htmx".parse(), Ok(ScaffoldKind::Htmx)));
+
+        let err = "grpc"
+            .parse::<ScaffoldKind>()
+            .expect_err("unknown kind parsed");
+        assert_eq!(
+            err.to_string(),
+            "unknown scaffold kind `grpc`, expected one of: api, html, htmx"
+        );
+    }
 }
@@ -1,13 +1,14 @@
 use std::{collections::HashMap, env::current_dir, path::Path};
 
-use chrono::Utc;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use duct::cmd;
 use heck::ToUpperCamelCase;
-use rrgen::RRgen;
-use serde_json::json;
+use serde_json::{json, Value};
 
 use crate::{
-    get_mappings, infer::parse_field_type, render_template, AppInfo, Error, GenerateResults, Result,
+    get_mappings,
+    infer::{parse_field_type, split_default, FieldType},
+    render_template, AppInfo, Error, GenerateResults, Renderer, Result,
 };
 
 /// skipping some fields from the generated models.
@@ -33,7 +34,18 @@ pub fn get_columns_and_references(
             );
             continue;
         }
+        let (ftype, default) = split_default(ftype);
         let field_type = parse_field_type(ftype)?;
+        if default.is_some()
+            && !matches!(
+                field_type,
+                FieldType::Type(_) | FieldType::TypeWithParameters(..)
+            )
+        {
+            return Err(Error::Message(format!(
+                "field `{fname}`: references cannot have a default value."
+            )));
+        }
         match field_type {
             crate::infer::FieldType::Reference => {
                 // (users, "")
@@ -51,7 +63,11 @@ pub fn get_columns_and_references(
             crate::infer::FieldType::Type(ftype) => {
                 let mappings = get_mappings();
                 let col_type = mappings.col_type_field(ftype.as_str())?;
-                columns.push((fname.to_string(), col_type.to_string()));
+                let col = match default {
+                    Some(value) => col_type_with_default(fname, col_type, value)?,
+                    None => col_type.to_string(),
+                };
+                columns.push((fname.to_string(), col));
             }
             crate::infer::FieldType::TypeWithParameters(ftype, params) => {
                 let mappings = get_mappings();
@@ -89,6 +105,10 @@ pub fn get_columns_and_references(
                     }
                 };
 
+                let col = match default {
+                    Some(value) => col_type_with_default(fname, &col, value)?,
+                    None => col,
+                };
                 columns.push((fname.to_string(), col));
             }
         }
@@ -96,22 +116,121 @@ pub fn get_columns_and_references(
     Ok((columns, references))
 }
 
-pub fn generate(
-    rrgen: &RRgen,
+/// the `*WithDefault` column type for `col_type`, with `value` checked and
+/// written as a literal of the column's rust type, e.g.
+/// `StringWithDefault("active".to_string())` or `DecimalLenWithDefault(10,2, 1.5)`
+fn col_type_with_default(fname: &str, col_type: &str, value: &str) -> Result<String> {
+    let (variant, params) = match col_type.split_once('(') {
+        Some((variant, params)) => (variant, format!("{}, ", params.trim_end_matches(')'))),
+        None => (col_type, String::new()),
+    };
+    let invalid = |expected: &str| {
+        Error::Message(format!(
+            "field `{fname}`: default value `{value}` is not a valid {expected}."
+        ))
+    };
+
+    let literal = match variant {
+        "String" | "StringLen" | "Text" => format!("{value:?}.to_string()"),
+        "Char" | "CharLen" => {
+            let mut chars = value.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => format!("{c:?}"),
+                _ => return Err(invalid("single character")),
+            }
+        }
+        "Date" => {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .map_err(|_| invalid("date (YYYY-MM-DD)"))?;
+            format!("{value:?}.to_string()")
+        }
+        "DateTime" => {
+            NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+                .map_err(|_| invalid("date and time (YYYY-MM-DD HH:MM:SS)"))?;
+            format!("{value:?}.to_string()")
+        }
+        "Time" => {
+            NaiveTime::parse_from_str(value, "%H:%M:%S").map_err(|_| invalid("time (HH:MM:SS)"))?;
+            format!("{value:?}.to_string()")
+        }
+        "TimestampWithTimeZone" => {
+            DateTime::parse_from_rfc3339(value).map_err(|_| invalid("RFC 3339 timestamp"))?;
+            format!("{value:?}.to_string()")
+        }
+        "Integer" => value
+            .parse::<i32>()
+            .map_err(|_| invalid("i32"))?
+            .to_string(),
+        "SmallInteger" => value
+            .parse::<i16>()
+            .map_err(|_| invalid("i16"))?
+            .to_string(),
+        "BigInteger" => value
+            .parse::<i64>()
+            .map_err(|_| invalid("i64"))?
+            .to_string(),
+        "Unsigned" => value
+            .parse::<u32>()
+            .map_err(|_| invalid("u32"))?
+            .to_string(),
+        "SmallUnsigned" => value
+            .parse::<u16>()
+            .map_err(|_| invalid("u16"))?
+            .to_string(),
+        "BigUnsigned" => value
+            .parse::<u64>()
+            .map_err(|_| invalid("u64"))?
+            .to_string(),
+        "Boolean" => value
+            .parse::<bool>()
+            .map_err(|_| invalid("bool"))?
+            .to_string(),
+        "Float" => match value.parse::<f32>() {
+            Ok(value) if value.is_finite() => format!("{value:?}"),
+            _ => return Err(invalid("f32")),
+        },
+        "Decimal" | "DecimalLen" | "Double" | "Money" => match value.parse::<f64>() {
+            Ok(value) if value.is_finite() => format!("{value:?}"),
+            _ => return Err(invalid("f64")),
+        },
+        _ => {
+            return Err(Error::Message(format!(
+                "field `{fname}`: column type `{col_type}` cannot have a default value, only \
+                 required (`!`) string, char, text, number, bool, date and time types can."
+            )))
+        }
+    };
+    Ok(format!("{variant}WithDefault({params}{literal})"))
+}
+
+/// template variables of the `model` templates
+pub fn vars(
     name: &str,
     with_tz: bool,
     fields: &[(String, String)],
     appinfo: &AppInfo,
-) -> Result<GenerateResults> {
+) -> Result<Value> {
     let pkg_name: &str = &appinfo.app_name;
     let ts = Utc::now();
 
     let (columns, references) = get_columns_and_references(fields)?;
 
-    let vars = json!({"name": name, "ts": ts, "with_tz": with_tz,"pkg_name": pkg_name, "columns": columns, "references": references});
-    let gen_result = render_template(rrgen, Path::new("model"), &vars)?;
+    Ok(
+        json!({"name": name, "ts": ts, "with_tz": with_tz,"pkg_name": pkg_name, "columns": columns, "references": references}),
+    )
+}
 
-    if std::env::var("SKIP_MIGRATION").is_err() {
+pub fn generate(
+    renderer: &Renderer<'_>,
+    name: &str,
+    with_tz: bool,
+    fields: &[(String, String)],
+    appinfo: &AppInfo,
+) -> Result<GenerateResults> {
+    let vars = vars(name, with_tz, fields, appinfo)?;
+    let gen_result = render_template(renderer, Path::new("model"), &vars)?;
+
+    if renderer.options.run_migration && !renderer.is_preview() {
         // generate the model files by migrating and re-running seaorm
         let cwd = current_dir()?;
         let env_map: HashMap<_, _> = std::env::vars().collect();
@@ -186,6 +305,67 @@ mod tests {
         assert_eq!(res, (expected_columns, expected_references));
     }
 
+    #[test]
+    fn test_get_columns_with_default_values() {
+        let fields = [
+            to_field("status", "string!:default:active"),
+            to_field("views", "int!:default:0"),
+            to_field("published", "bool!:default:false"),
+            to_field("price", "decimal_len!:10:2:default:9.5"),
+            to_field("code", "string_len!:8:default:none"),
+            to_field("grade", "char!:default:A"),
+            to_field("tier", "char_len!:2:default:b"),
+            to_field("opens_at", "time!:default:09:30:00"),
+        ];
+        let res = get_columns_and_references(&fields).expect("Failed to parse fields");
+
+        let expected_columns = vec![
+            to_field("status", r#"StringWithDefault("active".to_string())"#),
+            to_field("views", "IntegerWithDefault(0)"),
+            to_field("published", "BooleanWithDefault(false)"),
+            to_field("price", "DecimalLenWithDefault(10,2, 9.5)"),
+            to_field("code", r#"StringLenWithDefault(8, "none".to_string())"#),
+            to_field("grade", "CharWithDefault('A')"),
+            to_field("tier", "CharLenWithDefault(2, 'b')"),
+            to_field("opens_at", r#"TimeWithDefault("09:30:00".to_string())"#),
+        ];
+        let expected_references: Vec<(String, String)> = vec![];
+
+        assert_eq!(res, (expected_columns, expected_references));
+    }
+
+    #[test]
+    fn test_reject_invalid_default_values() {
+        let err = get_columns_and_references(&[to_field("age", "int!:default:abc")])
+            .expect_err("non-numeric default accepted");
+        assert_eq!(
+            err.to_string(),
+            "field `age`: default value `abc` is not a valid i32."
+        );
+
+        let err = get_columns_and_references(&[to_field("opens_at", "time!:default:25:00:00")])
+            .expect_err("out of range time default accepted");
+        assert_eq!(
+            err.to_string(),
+            "field `opens_at`: default value `25:00:00` is not a valid time (HH:MM:SS)."
+        );
+
+        let err = get_columns_and_references(&[to_field("grade", "char!:default:AB")])
+            .expect_err("multi-character char default accepted");
+        assert_eq!(
+            err.to_string(),
+            "field `grade`: default value `AB` is not a valid single character."
+        );
+
+        let err = get_columns_and_references(&[to_field("status", "string:default:active")])
+            .expect_err("nullable column with default accepted");
+        assert!(err
+            .to_string()
+            .contains("`StringNull` cannot have a default value"));
+
+        assert!(get_columns_and_references(&[to_field("user", "references:default:1")]).is_err());
+    }
+
     #[test]
     fn test_get_references_from_fields() {
         let fields = [
@@ -0,0 +1,243 @@
+//! Render generators without writing anything.
+//!
+//! rrgen renders a template and writes its output in one step, and keeps its
+//! front matter, filters and file system private. Previews therefore render
+//! with their own [`Tera`], carrying the same filters, and apply the front
+//! matter the way [`rrgen::RRgen::generate`] does, against a read-only view of
+//! the project in which the files written so far are kept in memory.
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use cruet::string::pluralize::to_plural;
+use heck::{ToKebabCase, ToLowerCamelCase, ToSnakeCase, ToTitleCase, ToUpperCamelCase};
+use regex::Regex;
+use serde::Deserialize;
+use tera::{Context, Tera, Value};
+
+use crate::{tera_ext, Error, GenResult, RenderedFile, Result};
+
+/// Front matter of a template, as rrgen reads it
+#[derive(Deserialize)]
+struct FrontMatter {
+    to: String,
+    #[serde(default)]
+    skip_exists: bool,
+    #[serde(default)]
+    skip_glob: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    injections: Option<Vec<Injection>>,
+}
+
+#[derive(Deserialize)]
+struct Injection {
+    into: String,
+    content: String,
+    #[serde(default, with = "serde_regex")]
+    skip_if: Option<Regex>,
+    #[serde(default, with = "serde_regex")]
+    before: Option<Regex>,
+    #[serde(default, with = "serde_regex")]
+    before_last: Option<Regex>,
+    #[serde(default, with = "serde_regex")]
+    after: Option<Regex>,
+    #[serde(default, with = "serde_regex")]
+    after_last: Option<Regex>,
+    #[serde(default, with = "serde_regex")]
+    remove_lines: Option<Regex>,
+    #[serde(default)]
+    prepend: bool,
+    #[serde(default)]
+    append: bool,
+}
+
+impl Injection {
+    /// The content of `file` once injected into
+    fn apply(&self, file: &str) -> String {
+        let content = self.content.as_str();
+        let lines = file.lines().collect::<Vec<_>>();
+        let insert = |pos: Option<usize>| {
+            let mut lines = lines.clone();
+            if let Some(pos) = pos {
+                lines.insert(pos, content);
+            }
+            lines.join("\n")
+        };
+
+        if self.prepend {
+            format!("{content}\n{file}")
+        } else if self.append {
+            format!("{file}\n{content}")
+        } else if let Some(before) = &self.before {
+            insert(lines.iter().position(|ln| before.is_match(ln)))
+        } else if let Some(before_last) = &self.before_last {
+            insert(lines.iter().rposition(|ln| before_last.is_match(ln)))
+        } else if let Some(after) = &self.after {
+            insert(
+                lines
+                    .iter()
+                    .position(|ln| after.is_match(ln))
+                    .map(|pos| pos + 1),
+            )
+        } else if let Some(after_last) = &self.after_last {
+            insert(
+                lines
+                    .iter()
+                    .rposition(|ln| after_last.is_match(ln))
+                    .map(|pos| pos + 1),
+            )
+        } else if let Some(remove_lines) = &self.remove_lines {
+            lines
+                .iter()
+                .filter(|line| !remove_lines.is_match(line))
+                .copied()
+                .collect::<Vec<_>>()
+                .join("\n")
+        } else {
+            file.to_string()
+        }
+    }
+}
+
+/// Renders templates into memory
+pub(crate) struct Preview {
+    tera: Tera,
+    /// Project the files are generated into, if any. Without one, every file
+    /// is new and injections apply to empty files.
+    root: Option<PathBuf>,
+    /// Contents of the files written so far, by path relative to the root
+    written: RefCell<HashMap<PathBuf, String>>,
+    files: RefCell<Vec<RenderedFile>>,
+}
+
+impl Preview {
+    pub fn new(root: Option<&Path>) -> Self {
+        let mut tera = tera_ext::new();
+        register_filters(&mut tera);
+        Self {
+            tera,
+            root: root.map(Path::to_path_buf),
+            written: RefCell::new(HashMap::new()),
+            files: RefCell::new(vec![]),
+        }
+    }
+
+    pub fn into_files(self) -> Vec<RenderedFile> {
+        self.files.into_inner()
+    }
+
+    /// Whether `path`, relative to the root, exists
+    fn exists(&self, path: &Path) -> bool {
+        self.written.borrow().contains_key(path)
+            || self
+                .root
+                .as_ref()
+                .is_some_and(|root| root.join(path).exists())
+    }
+
+    /// Current content of `path`, relative to the root
+    fn read(&self, path: &Path) -> Result<String> {
+        if let Some(content) = self.written.borrow().get(path) {
+            return Ok(content.clone());
+        }
+        match &self.root {
+            Some(root) => Ok(fs::read_to_string(root.join(path))?),
+            None => Ok(String::new()),
+        }
+    }
+
+    /// Renders `input`, rendered from `template`, and records the file it
+    /// generates, following the same rules as [`rrgen::RRgen::generate`].
+    pub fn render(&self, template: &Path, input: &str, vars: &Value) -> Result<GenResult> {
+        let context = Context::from_serialize(vars).map_err(rrgen::Error::from)?;
+        let rendered = self
+            .tera
+            .clone()
+            .render_str(input, &context)
+            .map_err(rrgen::Error::from)?
+            .replace("\r\n", "\n");
+        let (frontmatter, body) = rendered.split_once("---\n").ok_or_else(|| {
+            Error::Message(format!(
+                "cannot split {} to frontmatter and body",
+                template.display()
+            ))
+        })?;
+        let frontmatter: FrontMatter =
+            serde_yaml::from_str(frontmatter).map_err(rrgen::Error::from)?;
+
+        let path = PathBuf::from(&frontmatter.to);
+        if frontmatter.skip_exists && self.exists(&path) {
+            return Ok(GenResult::Skipped);
+        }
+        if let Some(skip_glob) = &frontmatter.skip_glob {
+            // rrgen matches the pattern from the current directory, not the root
+            if glob::glob(skip_glob).map_err(rrgen::Error::from)?.count() > 0 {
+                return Ok(GenResult::Skipped);
+            }
+        }
+        self.written
+            .borrow_mut()
+            .insert(path.clone(), body.to_string());
+
+        let mut injected = vec![];
+        for injection in frontmatter.injections.unwrap_or_default() {
+            let into = PathBuf::from(&injection.into);
+            if self.root.is_some() && !self.exists(&into) {
+                return Err(Error::Message(format!(
+                    "cannot inject into {}: file does not exist",
+                    injection.into,
+                )));
+            }
+            let file = self.read(&into)?;
+            if injection
+                .skip_if
+                .as_ref()
+                .is_some_and(|skip_if| skip_if.is_match(&file))
+            {
+                continue;
+            }
+            self.written
+                .borrow_mut()
+                .insert(into, injection.apply(&file));
+            injected.push(injection.into);
+        }
+
+        self.files.borrow_mut().push(RenderedFile {
+            template: template.to_path_buf(),
+            path: frontmatter.to,
+            content: body.to_string(),
+            injections: injected,
+        });
+        Ok(GenResult::Generated {
+            message: frontmatter.message,
+        })
+    }
+}
+
+/// Converts the text a filter is applied to
+type Case = fn(&str) -> String;
+
+/// Registers the filters rrgen adds to its template engine
+fn register_filters(tera: &mut Tera) {
+    let filters: [(&str, Case); 8] = [
+        ("pascal_case", |s| s.to_upper_camel_case()),
+        ("camel_case", |s| s.to_lower_camel_case()),
+        ("kebab_case", |s| s.to_kebab_case()),
+        ("lower_case", str::to_lowercase),
+        ("snake_case", |s| s.to_snake_case()),
+        ("title_case", |s| s.to_title_case()),
+        ("upper_case", str::to_uppercase),
+        ("plural", to_plural),
+    ];
+    for (name, filter) in filters {
+        tera.register_filter(name, move |value: &Value, _: &HashMap<String, Value>| {
+            let s = tera::try_get_value!(name, "value", String, value);
+            Ok(Value::String(filter(&s)))
+        });
+    }
+}
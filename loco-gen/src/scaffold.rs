@@ -1,27 +1,33 @@
 use std::path::Path;
 
-use rrgen::RRgen;
 use serde_json::json;
 
 use crate::{
-    get_mappings, infer::parse_field_type, model, render_template, AppInfo, Error, GenerateResults,
-    Result, ScaffoldKind,
+    get_mappings,
+    infer::{parse_field_type, split_default},
+    model, render_template, AppInfo, Error, GenerateResults, Renderer, Result, ScaffoldKind,
 };
 
+/// pseudo field type for uploaded files. the column holds the storage key of
+/// the uploaded file, and the generated controller accepts multipart requests.
+pub const FILE_FIELD_TYPE: &str = "file";
+
 pub fn generate(
-    rrgen: &RRgen,
+    renderer: &Renderer<'_>,
     name: &str,
     with_tz: bool,
     fields: &[(String, String)],
     kind: &ScaffoldKind,
+    with_tracing: bool,
     appinfo: &AppInfo,
 ) -> Result<GenerateResults> {
     // - scaffold is never a link table
     // - never run with migration_only, because the controllers will refer to the
     //   models. the models only arrive after migration and entities sync.
-    let mut gen_result = model::generate(rrgen, name, with_tz, fields, appinfo)?;
+    let mut gen_result = model::generate(renderer, name, with_tz, fields, appinfo)?;
 
     let mut columns = Vec::new();
+    let mut file_columns = Vec::new();
     for (fname, ftype) in fields {
         if model::IGNORE_FIELDS.contains(&fname.as_str()) {
             tracing::warn!(
@@ -31,6 +37,8 @@ pub fn generate(
             continue;
         }
 
+        // defaults only change the migration, model::generate checks them
+        let (ftype, _default) = split_default(ftype);
         let field_type = parse_field_type(ftype)?;
         match field_type {
             crate::infer::FieldType::Reference => {
@@ -54,6 +62,9 @@ pub fn generate(
             crate::infer::FieldType::Type(ftype) => {
                 let mappings = get_mappings();
                 let rust_type = mappings.rust_field(ftype.as_str())?;
+                if ftype == FILE_FIELD_TYPE {
+                    file_columns.push(fname.to_string());
+                }
                 columns.push((fname.to_string(), rust_type.to_string(), ftype));
             }
             crate::infer::FieldType::TypeWithParameters(ftype, params) => {
@@ -74,20 +85,33 @@ pub fn generate(
         }
     }
 
-    let vars = json!({"name": name, "columns": columns, "pkg_name": appinfo.app_name});
+    // binary columns are exchanged as base64 strings by the API scaffold
+    let binary_columns = columns
+        .iter()
+        .filter(|(_, rust_type, _)| rust_type == "Vec<u8>" || rust_type == "Option<Vec<u8>>")
+        .map(|(name, _, _)| name.clone())
+        .collect::<Vec<_>>();
+    let vars = json!({
+        "name": name,
+        "columns": columns,
+        "file_columns": file_columns,
+        "binary_columns": binary_columns,
+        "with_tracing": with_tracing,
+        "pkg_name": appinfo.app_name,
+    });
     match kind {
         ScaffoldKind::Api => {
-            let res = render_template(rrgen, Path::new("scaffold/api"), &vars)?;
+            let res = render_template(renderer, Path::new("scaffold/api"), &vars)?;
             gen_result.rrgen.extend(res.rrgen);
             gen_result.local_templates.extend(res.local_templates);
         }
         ScaffoldKind::Html => {
-            let res = render_template(rrgen, Path::new("scaffold/html"), &vars)?;
+            let res = render_template(renderer, Path::new("scaffold/html"), &vars)?;
             gen_result.rrgen.extend(res.rrgen);
             gen_result.local_templates.extend(res.local_templates);
         }
         ScaffoldKind::Htmx => {
-            let res = render_template(rrgen, Path::new("scaffold/htmx"), &vars)?;
+            let res = render_template(renderer, Path::new("scaffold/htmx"), &vars)?;
             gen_result.rrgen.extend(res.rrgen);
             gen_result.local_templates.extend(res.local_templates);
         }
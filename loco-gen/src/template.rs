@@ -1,4 +1,7 @@
-use std::path::{Path, PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 use include_dir::{include_dir, Dir, DirEntry, File};
 
@@ -7,6 +10,63 @@ use crate::{Error, Result};
 static TEMPLATES: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/src/templates");
 pub const DEFAULT_LOCAL_TEMPLATE: &str = ".loco-templates";
 
+/// Where overrides of the built-in templates come from. Templates without an
+/// override are rendered from the built-in ones.
+pub trait TemplateSource: Send + Sync {
+    /// Returns the override for the built-in template at `path` (e.g.
+    /// `model/model.t`) with the location it was read from.
+    ///
+    /// # Errors
+    ///
+    /// When an override exists but cannot be read.
+    fn find(&self, path: &Path) -> Result<Option<(PathBuf, String)>>;
+}
+
+/// No overrides, only the built-in templates are used.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BuiltinTemplateSource;
+
+impl TemplateSource for BuiltinTemplateSource {
+    fn find(&self, _path: &Path) -> Result<Option<(PathBuf, String)>> {
+        Ok(None)
+    }
+}
+
+/// Overrides kept in a directory that mirrors the built-in templates, such as
+/// a project's [`DEFAULT_LOCAL_TEMPLATE`] directory.
+#[derive(Debug, Clone)]
+pub struct FileSystemTemplateSource {
+    dir: PathBuf,
+}
+
+impl FileSystemTemplateSource {
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+impl Default for FileSystemTemplateSource {
+    /// [`DEFAULT_LOCAL_TEMPLATE`] relative to the current directory.
+    fn default() -> Self {
+        Self::new(DEFAULT_LOCAL_TEMPLATE)
+    }
+}
+
+impl TemplateSource for FileSystemTemplateSource {
+    fn find(&self, path: &Path) -> Result<Option<(PathBuf, String)>> {
+        let custom_template = self.dir.join(path);
+        if !custom_template.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&custom_template).map_err(|err| {
+            tracing::error!(custom_template = %custom_template.display(), "could not read custom template");
+            err
+        })?;
+        Ok(Some((custom_template, content)))
+    }
+}
+
 /// Returns a list of paths that should be ignored during file collection.
 #[must_use]
 pub fn get_ignored_paths() -> Vec<&'static Path> {
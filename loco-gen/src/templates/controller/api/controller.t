@@ -6,10 +6,16 @@ message: "Controller `{{module_name}}` was added successfully."
 injections:
 - into: src/controllers/mod.rs
   append: true
+  skip_if: '(?m)^pub mod {{ file_name }};$'
   content: "pub mod {{ file_name }};"
 - into: src/app.rs
   after: "AppRoutes::"
+  skip_if: 'controllers::{{ file_name }}::routes\(\)'
   content: "            .add_route(controllers::{{ file_name }}::routes())"
+- into: src/app.rs
+  append: true
+  skip_if: 'controllers::{{ file_name }}::routes\(\)'
+  content: "// TODO: `AppRoutes::` was not found, register the {{ file_name }} routes manually:\n//     .add_route(controllers::{{ file_name }}::routes())"
 ---
 #![allow(clippy::missing_errors_doc)]
 #![allow(clippy::unnecessary_struct_initialization)]
@@ -31,7 +37,7 @@ pub async fn {{action}}(State(_ctx): State<AppContext>) -> Result<Response> {
 
 pub fn routes() -> Routes {
     Routes::new()
-        .prefix("api/{{file_name | plural}}/")
+        .prefix("api/{{plural_file_name}}/")
         .add("/", get(index))
         {%- for action in actions %}
         .add("{{action}}", get({{action}}))
@@ -14,9 +14,9 @@ use serial_test::serial;
 
 #[tokio::test]
 #[serial]
-async fn can_get_{{ name | plural | snake_case }}() {
+async fn can_get_{{ plural_name | snake_case }}() {
     request::<App, _, _>(|request, _ctx| async move {
-        let res = request.get("/api/{{ name | plural | snake_case }}/").await;
+        let res = request.get("/api/{{ plural_name | snake_case }}/").await;
         assert_eq!(res.status_code(), 200);
 
         // you can assert content like this:
@@ -30,7 +30,7 @@ async fn can_get_{{ name | plural | snake_case }}() {
 #[serial]
 async fn can_get_{{action}}() {
     request::<App, _, _>(|request, _ctx| async move {
-        let res = request.get("/{{ name | plural | snake_case }}/{{action}}").await;
+        let res = request.get("/{{ plural_name | snake_case }}/{{action}}").await;
         assert_eq!(res.status_code(), 200);
     })
     .await;
@@ -6,10 +6,16 @@ message: "Controller `{{module_name}}` was added successfully."
 injections:
 - into: src/controllers/mod.rs
   append: true
+  skip_if: '(?m)^pub mod {{ file_name }};$'
   content: "pub mod {{ file_name }};"
 - into: src/app.rs
   after: "AppRoutes::"
+  skip_if: 'controllers::{{ file_name }}::routes\(\)'
   content: "            .add_route(controllers::{{ file_name }}::routes())"
+- into: src/app.rs
+  append: true
+  skip_if: 'controllers::{{ file_name }}::routes\(\)'
+  content: "// TODO: `AppRoutes::` was not found, register the {{ file_name }} routes manually:\n//     .add_route(controllers::{{ file_name }}::routes())"
 ---
 #![allow(clippy::missing_errors_doc)]
 #![allow(clippy::unnecessary_struct_initialization)]
@@ -29,7 +35,7 @@ pub async fn {{action}}(
 
 pub fn routes() -> Routes {
     Routes::new()
-        .prefix("{{file_name | plural}}/")
+        .prefix("{{plural_file_name}}/")
         {%- for action in actions %}
         .add("{{action}}", get({{action}}))
         {%- endfor %}
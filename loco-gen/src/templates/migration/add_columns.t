@@ -1,6 +1,6 @@
 {% set mig_ts = ts | date(format="%Y%m%d_%H%M%S") -%}
 {% set mig_name = name | snake_case -%}
-{% set plural_snake = table | plural | snake_case -%}
+{% set plural_snake = plural_table | snake_case -%}
 {% set module_name = "m" ~  mig_ts ~ "_" ~ mig_name -%}
 to: "migration/src/{{module_name}}.rs"
 skip_glob: "migration/src/m????????_??????_{{mig_name}}.rs"
@@ -1,7 +1,7 @@
 {% set mig_ts = ts | date(format="%Y%m%d_%H%M%S") -%}
-{% set plural_snake = name | plural | snake_case -%}
+{% set plural_snake = plural_name | snake_case -%}
 {% set module_name = "m" ~  mig_ts ~ "_" ~ plural_snake -%}
-{% set plural_snake = table | plural | snake_case -%}
+{% set plural_snake = plural_table | snake_case -%}
 {% if with_tz %}
 {% set join_table_func = "create_join_table" %}
 {% else %}
@@ -1,7 +1,7 @@
 {% set mig_ts = ts | date(format="%Y%m%d_%H%M%S") -%}
-{% set plural_snake = name | plural | snake_case -%}
+{% set plural_snake = plural_name | snake_case -%}
 {% set module_name = "m" ~  mig_ts ~ "_" ~ plural_snake -%}
-{% set model = name | plural | pascal_case -%}
+{% set model = plural_name | pascal_case -%}
 {% if with_tz %}
 {% set create_table_func = "create_table" %}
 {% else %}
@@ -1,5 +1,5 @@
-{% set plural_snake = name | plural | snake_case -%}
-{% set model = name | plural | pascal_case -%}
+{% set plural_snake = plural_name | snake_case -%}
+{% set model = plural_name | pascal_case -%}
 to: "tests/models/{{plural_snake}}.rs"
 message: "A test for model `{{model}}` was added. Run with `cargo test`."
 skip_exists: true
@@ -1,27 +1,48 @@
 {% set file_name = name |  snake_case -%}
 {% set module_name = file_name | pascal_case -%}
+{% set with_files = file_columns | length > 0 -%}
+{% set with_binary = binary_columns | length > 0 -%}
 to: src/controllers/{{ file_name }}.rs
 skip_exists: true
 message: "Controller `{{module_name}}` was added successfully."
 injections:
 - into: src/controllers/mod.rs
   append: true
+  skip_if: '(?m)^pub mod {{ file_name }};$'
   content: "pub mod {{ file_name }};"
 - into: src/app.rs
   after: "AppRoutes::"
+  skip_if: 'controllers::{{ file_name }}::routes\(\)'
   content: "            .add_route(controllers::{{ file_name }}::routes())"
+- into: src/app.rs
+  append: true
+  skip_if: 'controllers::{{ file_name }}::routes\(\)'
+  content: "// TODO: `AppRoutes::` was not found, register the {{ file_name }} routes manually:\n//     .add_route(controllers::{{ file_name }}::routes())"
 ---
 #![allow(clippy::missing_errors_doc)]
 #![allow(clippy::unnecessary_struct_initialization)]
 #![allow(clippy::unused_async)]
+{% if with_binary -%}
+use loco_rs::controller::base64;
+{% endif -%}
+{% if with_files -%}
+use loco_rs::controller::upload::UploadForm;
+{% endif -%}
 use loco_rs::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::models::_entities::{{file_name | plural}}::{ActiveModel, Entity, Model};
+use crate::models::_entities::{{plural_file_name}}::{ActiveModel, Entity, Model};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Params {
     {% for column in columns -%}
+    {%- if column.0 in binary_columns -%}
+    {%- if column.1 == "Vec<u8>" -%}
+    #[serde(with = "base64")]
+    {%- else -%}
+    #[serde(default, with = "base64::option")]
+    {%- endif %}
+    {% endif -%}
     {%- if column.2 == "IntegerNull" -%}
     pub {{column.0}}: Option<i32>,
     {%- else -%}
@@ -33,18 +54,52 @@ pub struct Params {
 impl Params {
     fn update(&self, item: &mut ActiveModel) {
       {% for column in columns -%}
-      {%- if "Vec<" in column.1 -%}
+      {%- if column.2 == "file" -%}
+      if self.{{column.0}}.is_some() {
+          item.{{column.0}} = Set(self.{{column.0}}.clone());
+      }
+      {%- elif "Vec<" in column.1 -%}
       item.{{column.0}} = Set(self.{{column.0}}.clone());
       {%- elif column.2 == "IntegerNull" -%}
       item.{{column.0}} = Set(self.{{column.0}});
-      {%- elif "i32" in column.1 or "i64" in column.1 or "i16" in column.1 or "Uuid" in column.1 or "f32" in column.1 or "f64" in column.1 or "Decimal" in column.1 or "bool" in column.1 or "Date" in column.1 or "DateTime" in column.1 or "DateTimeWithTimeZone" in column.1 -%}
+      {%- elif "i32" in column.1 or "i64" in column.1 or "i16" in column.1 or "Uuid" in column.1 or "f32" in column.1 or "f64" in column.1 or "Decimal" in column.1 or "bool" in column.1 or "Date" in column.1 or "DateTime" in column.1 or "DateTimeWithTimeZone" in column.1 or "Time" in column.1 -%}
       item.{{column.0}} = Set(self.{{column.0}});
       {%- else -%}
       item.{{column.0}} = Set(self.{{column.0}}.clone());
       {%- endif %}
       {% endfor -%}
     }
+    {%- if with_files %}
+
+    async fn from_multipart(ctx: &AppContext, multipart: Multipart) -> Result<Self> {
+        UploadForm::new("{{plural_file_name}}")
+            {% for column in columns -%}
+            {%- if column.2 == "file" -%}
+            .file("{{column.0}}")
+            {% endif -%}
+            {% endfor -%}
+            .parse(ctx, multipart)
+            .await
+    }
+    {%- endif %}
+}
+{%- if with_binary %}
+
+/// Serializes an item with its binary fields encoded as base64 strings.
+fn to_json(item: &Model) -> serde_json::Value {
+    let mut value = serde_json::json!(item);
+    {% for column in columns -%}
+    {%- if column.0 in binary_columns -%}
+    {%- if column.1 == "Vec<u8>" -%}
+    value["{{column.0}}"] = base64::encode(&item.{{column.0}}).into();
+    {%- else -%}
+    value["{{column.0}}"] = item.{{column.0}}.as_deref().map(base64::encode).into();
+    {%- endif %}
+    {% endif -%}
+    {% endfor -%}
+    value
 }
+{%- endif %}
 
 async fn load_item(ctx: &AppContext, id: i32) -> Result<Model> {
     let item = Entity::find_by_id(id).one(&ctx.db).await?;
@@ -52,47 +107,121 @@ async fn load_item(ctx: &AppContext, id: i32) -> Result<Model> {
 }
 
 #[debug_handler]
-pub async fn list(State(ctx): State<AppContext>) -> Result<Response> {
-    format::json(Entity::find().all(&ctx.db).await?)
+{% if with_tracing -%}
+#[tracing::instrument(
+    name = "{{plural_file_name}}.list",
+    skip_all,
+    fields(model = "{{file_name}}", action = "list", db.query_count = tracing::field::Empty)
+)]
+{% endif -%}
+pub async fn list(
+    State(ctx): State<AppContext>,
+    Query(pagination): Query<query::PaginationQuery>,
+) -> Result<Response> {
+    {%- if with_tracing %}
+    let _queries = loco_rs::db::QueryCount::start();
+    {%- endif %}
+    let res = query::paginate(&ctx.db, Entity::find(), None, &pagination).await?;
+    Ok(PaginatedResponse::from_page(res, &pagination)
+        {%- if with_binary %}
+        .map(|item| to_json(&item))
+        {%- endif %}
+        .with_links("/api/{{plural_file_name}}")
+        .into_response())
 }
 
 #[debug_handler]
+{% if with_tracing -%}
+#[tracing::instrument(
+    name = "{{plural_file_name}}.add",
+    skip_all,
+    fields(model = "{{file_name}}", action = "add", db.query_count = tracing::field::Empty)
+)]
+{% endif -%}
+{% if with_files -%}
+pub async fn add(State(ctx): State<AppContext>, multipart: Multipart) -> Result<Response> {
+    let params = Params::from_multipart(&ctx, multipart).await?;
+{%- else -%}
 pub async fn add(State(ctx): State<AppContext>, Json(params): Json<Params>) -> Result<Response> {
+{%- endif %}
+    {%- if with_tracing %}
+    let _queries = loco_rs::db::QueryCount::start();
+    {%- endif %}
     let mut item = ActiveModel {
         ..Default::default()
     };
     params.update(&mut item);
     let item = item.insert(&ctx.db).await?;
-    format::json(item)
+    format::json({% if with_binary %}to_json(&item){% else %}item{% endif %})
 }
 
 #[debug_handler]
+{% if with_tracing -%}
+#[tracing::instrument(
+    name = "{{plural_file_name}}.update",
+    skip_all,
+    fields(model = "{{file_name}}", action = "update", db.query_count = tracing::field::Empty)
+)]
+{% endif -%}
 pub async fn update(
     Path(id): Path<i32>,
     State(ctx): State<AppContext>,
+    {% if with_files -%}
+    multipart: Multipart,
+) -> Result<Response> {
+    let params = Params::from_multipart(&ctx, multipart).await?;
+    {%- else -%}
     Json(params): Json<Params>,
 ) -> Result<Response> {
+    {%- endif %}
+    {%- if with_tracing %}
+    let _queries = loco_rs::db::QueryCount::start();
+    {%- endif %}
     let item = load_item(&ctx, id).await?;
     let mut item = item.into_active_model();
     params.update(&mut item);
     let item = item.update(&ctx.db).await?;
-    format::json(item)
+    format::json({% if with_binary %}to_json(&item){% else %}item{% endif %})
 }
 
 #[debug_handler]
+{% if with_tracing -%}
+#[tracing::instrument(
+    name = "{{plural_file_name}}.remove",
+    skip_all,
+    fields(model = "{{file_name}}", action = "remove", db.query_count = tracing::field::Empty)
+)]
+{% endif -%}
 pub async fn remove(Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    {%- if with_tracing %}
+    let _queries = loco_rs::db::QueryCount::start();
+    {%- endif %}
     load_item(&ctx, id).await?.delete(&ctx.db).await?;
     format::empty()
 }
 
 #[debug_handler]
+{% if with_tracing -%}
+#[tracing::instrument(
+    name = "{{plural_file_name}}.get_one",
+    skip_all,
+    fields(model = "{{file_name}}", action = "get_one", db.query_count = tracing::field::Empty)
+)]
+{% endif -%}
 pub async fn get_one(Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    {%- if with_tracing %}
+    let _queries = loco_rs::db::QueryCount::start();
+    {%- endif %}
+    {%- if with_binary %}
+    format::json(to_json(&load_item(&ctx, id).await?))
+    {%- else %}
     format::json(load_item(&ctx, id).await?)
+    {%- endif %}
 }
 
 pub fn routes() -> Routes {
     Routes::new()
-        .prefix("api/{{file_name | plural}}/")
+        .prefix("api/{{plural_file_name}}/")
         .add("/", get(list))
         .add("/", post(add))
         .add("{id}", get(get_one))
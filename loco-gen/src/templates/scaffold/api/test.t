@@ -14,13 +14,19 @@ use serial_test::serial;
 
 #[tokio::test]
 #[serial]
-async fn can_get_{{ name | plural | snake_case }}() {
+async fn can_get_{{ plural_name | snake_case }}() {
     request::<App, _, _>(|request, _ctx| async move {
-        let res = request.get("/api/{{ name | plural | snake_case }}/").await;
+        let res = request.get("/api/{{ plural_name | snake_case }}/").await;
         assert_eq!(res.status_code(), 200);
 
-        // you can assert content like this:
-        // assert_eq!(res.text(), "content");
+        // the list is wrapped in a pagination envelope
+        let body: serde_json::Value = res.json();
+        assert!(body["data"].is_array());
+        for key in ["page", "per_page", "total"] {
+            assert!(body[key].is_u64(), "`{key}` is missing from {body}");
+        }
+        assert!(body["links"]["first"].is_string());
+        assert!(body["links"]["last"].is_string());
     })
     .await;
 }
@@ -10,14 +10,14 @@ injections:
 ---
 use loco_rs::prelude::*;
 
-use crate::models::_entities::{{file_name | plural}};
+use crate::models::_entities::{{plural_file_name}};
 
-/// Render a list view of `{{name | plural}}`.
+/// Render a list view of `{{plural_name}}`.
 ///
 /// # Errors
 ///
 /// When there is an issue with rendering the view.
-pub fn list(v: &impl ViewRenderer, items: &Vec<{{file_name | plural}}::Model>) -> Result<Response> {
+pub fn list(v: &impl ViewRenderer, items: &Vec<{{plural_file_name}}::Model>) -> Result<Response> {
     format::render().view(v, "{{file_name}}/list.html", data!({"items": items}))
 }
 
@@ -26,7 +26,7 @@ pub fn list(v: &impl ViewRenderer, items: &Vec<{{file_name | plural}}::Model>) -
 /// # Errors
 ///
 /// When there is an issue with rendering the view.
-pub fn show(v: &impl ViewRenderer, item: &{{file_name | plural}}::Model) -> Result<Response> {
+pub fn show(v: &impl ViewRenderer, item: &{{plural_file_name}}::Model) -> Result<Response> {
     format::render().view(v, "{{file_name}}/show.html", data!({"item": item}))
 }
 
@@ -44,6 +44,6 @@ pub fn create(v: &impl ViewRenderer) -> Result<Response> {
 /// # Errors
 ///
 /// When there is an issue with rendering the view.
-pub fn edit(v: &impl ViewRenderer, item: &{{file_name | plural}}::Model) -> Result<Response> {
+pub fn edit(v: &impl ViewRenderer, item: &{{plural_file_name}}::Model) -> Result<Response> {
     format::render().view(v, "{{file_name}}/edit.html", data!({"item": item}))
 }
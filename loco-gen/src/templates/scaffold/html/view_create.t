@@ -16,7 +16,7 @@ Create new {{name}}
 
 {% raw %}{% block content %}{% endraw %}
 <div class="mb-10">
-    <form action="/{{name | plural}}" method="post" class="flex-1 lg:max-w-2xl">
+    <form action="/{{plural_name}}" method="post"{% if file_columns | length > 0 %} enctype="multipart/form-data"{% endif %} class="flex-1 lg:max-w-2xl">
     {% for column in columns -%}
             {{ render_form_field(fname=column.0, rust_type=column.1, ftype=column.2)}}
         {% endfor -%}
@@ -25,7 +25,7 @@ Create new {{name}}
         </div>
     </form>
 <br />
-<a href="/{{name | plural}}">Back to {{name | plural}}</a>
+<a href="/{{plural_name}}">Back to {{plural_name}}</a>
 </div>
 {% raw %}{% endblock content %}{% endraw %}
 
@@ -16,7 +16,7 @@ Edit {{name}}: {% raw %}{{ item.id }}{% endraw %}
 
 {% raw %}{% block content %}{% endraw %}
 <div class="mb-10">
-    <form action="/{{name | plural}}/{% raw %}{{ item.id }}{% endraw %}" method="post" class="flex-1 lg:max-w-2xl">
+    <form action="/{{plural_name}}/{% raw %}{{ item.id }}{% endraw %}" method="post"{% if file_columns | length > 0 %} enctype="multipart/form-data"{% endif %} class="flex-1 lg:max-w-2xl">
     {% for column in columns -%}
             {{ render_form_field(fname=column.0, rust_type=column.1, ftype=column.2, edit_form=true)}}
         {% endfor -%}
@@ -24,13 +24,13 @@ Edit {{name}}: {% raw %}{{ item.id }}{% endraw %}
             <div class="mt-5">
                 <button class=" text-xs py-3 px-6 rounded-lg bg-gray-900 text-white" type="submit">Submit</button>
                 <button class="text-xs py-3 px-6 rounded-lg bg-red-600 text-white"
-                            onclick="confirmDelete(event, '/{{name | plural}}/{% raw %}{{ item.id }}{% endraw %}', '/{{name | plural}}' )">Delete</button>
+                            onclick="confirmDelete(event, '/{{plural_name}}/{% raw %}{{ item.id }}{% endraw %}', '/{{plural_name}}' )">Delete</button>
             </div>
         </div> 
     </form>
     <div id="success-message" class="mt-4"></div>
     <br />
-    <a href="/{{name | plural}}">Back to {{name}}</a>
+    <a href="/{{plural_name}}">Back to {{name}}</a>
 </div>
 {% raw %}{% endblock content %}{% endraw %}
 
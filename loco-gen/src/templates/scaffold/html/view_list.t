@@ -44,7 +44,7 @@ List of {{file_name}}
                         </td>
                         {% endfor -%}
                         <td>
-                            <a href="/{{name | plural}}/{% raw %}{{ item.id }}{% endraw %}/edit">Edit</a>
+                            <a href="/{{plural_name}}/{% raw %}{{ item.id }}{% endraw %}/edit">Edit</a>
                         </td>
                     </tr>
                     {% raw %}{% endfor %}{% endraw %}
@@ -54,7 +54,7 @@ List of {{file_name}}
     
         <div class="flex">
             <div class="ml-auto  p-4">
-                <a href="/{{name | plural}}/new"
+                <a href="/{{plural_name}}/new"
                     class="mt-5 bg-blue-500 text-white bg-primary-600 hover:bg-primary-700 focus:ring-4 focus:outline-none focus:ring-primary-300 font-medium rounded-lg text-sm px-5 py-2.5 text-center dark:bg-primary-600 dark:hover:bg-primary-700 dark:focus:ring-primary-800">
                     Create
                 </a>
@@ -68,7 +68,7 @@ List of {{file_name}}
         <div class="bg-white rounded-lg shadow-lg p-8 max-w-4xl w-full flex flex-col items-center">
             <h3 class="font-bold text-lg">Nothing Here Yet</h3>
             There are no records to display. Add a new record to get started!
-            <a href="/{{name | plural}}/new"
+            <a href="/{{plural_name}}/new"
             class="mt-5 bg-blue-500 text-white bg-primary-600 hover:bg-primary-700 focus:ring-4 focus:outline-none focus:ring-primary-300 font-medium rounded-lg text-sm px-5 py-2.5 text-center dark:bg-primary-600 dark:hover:bg-primary-700 dark:focus:ring-primary-800">
             Create
         </a>
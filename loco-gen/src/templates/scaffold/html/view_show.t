@@ -19,6 +19,6 @@ View {{name}}: {% raw %}{{ item.id }}{% endraw %}
     </div>
 {% endfor -%}
 <br />
-<a href="/{{name | plural}}">Back to {{name | plural}}</a>
+<a href="/{{plural_name}}">Back to {{plural_name}}</a>
 </div>
 {% raw %}{% endblock content %}{% endraw %}
\ No newline at end of file
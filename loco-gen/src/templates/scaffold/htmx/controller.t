@@ -1,25 +1,35 @@
 {% set file_name = name |  snake_case -%}
 {% set module_name = file_name | pascal_case -%}
+{% set with_files = file_columns | length > 0 -%}
 to: src/controllers/{{ file_name }}.rs
 skip_exists: true
 message: "Controller `{{module_name}}` was added successfully."
 injections:
 - into: src/controllers/mod.rs
   append: true
+  skip_if: '(?m)^pub mod {{ file_name }};$'
   content: "pub mod {{ file_name }};"
 - into: src/app.rs
   after: "AppRoutes::"
+  skip_if: 'controllers::{{ file_name }}::routes\(\)'
   content: "            .add_route(controllers::{{ file_name }}::routes())"
+- into: src/app.rs
+  append: true
+  skip_if: 'controllers::{{ file_name }}::routes\(\)'
+  content: "// TODO: `AppRoutes::` was not found, register the {{ file_name }} routes manually:\n//     .add_route(controllers::{{ file_name }}::routes())"
 ---
 #![allow(clippy::missing_errors_doc)]
 #![allow(clippy::unnecessary_struct_initialization)]
 #![allow(clippy::unused_async)]
+{% if with_files -%}
+use loco_rs::controller::upload::UploadForm;
+{% endif -%}
 use loco_rs::prelude::*;
 use serde::{Deserialize, Serialize};
 use sea_orm::{sea_query::Order, QueryOrder};
 
 use crate::{
-    models::_entities::{{file_name | plural}}::{ActiveModel, Column, Entity, Model},
+    models::_entities::{{plural_file_name}}::{ActiveModel, Column, Entity, Model},
     views,
 };
 
@@ -37,17 +47,34 @@ pub struct Params {
 impl Params {
     fn update(&self, item: &mut ActiveModel) {
       {% for column in columns -%}
-      {%- if "Vec<" in column.1 -%}
+      {%- if column.2 == "file" -%}
+      if self.{{column.0}}.is_some() {
+          item.{{column.0}} = Set(self.{{column.0}}.clone());
+      }
+      {%- elif "Vec<" in column.1 -%}
       item.{{column.0}} = Set(self.{{column.0}}.clone());
       {%- elif column.2 == "IntegerNull" -%}
       item.{{column.0}} = Set(self.{{column.0}});
-      {%- elif "i32" in column.1 or "i64" in column.1 or "i16" in column.1 or "Uuid" in column.1 or "f32" in column.1 or "f64" in column.1 or "Decimal" in column.1 or "bool" in column.1 or "Date" in column.1 or "DateTime" in column.1 or "DateTimeWithTimeZone" in column.1 -%}
+      {%- elif "i32" in column.1 or "i64" in column.1 or "i16" in column.1 or "Uuid" in column.1 or "f32" in column.1 or "f64" in column.1 or "Decimal" in column.1 or "bool" in column.1 or "Date" in column.1 or "DateTime" in column.1 or "DateTimeWithTimeZone" in column.1 or "Time" in column.1 -%}
       item.{{column.0}} = Set(self.{{column.0}});
       {%- else -%}
       item.{{column.0}} = Set(self.{{column.0}}.clone());
       {%- endif %}
       {% endfor -%}
     }
+    {%- if with_files %}
+
+    async fn from_multipart(ctx: &AppContext, multipart: Multipart) -> Result<Self> {
+        UploadForm::new("{{plural_file_name}}")
+            {% for column in columns -%}
+            {%- if column.2 == "file" -%}
+            .file("{{column.0}}")
+            {% endif -%}
+            {% endfor -%}
+            .parse(ctx, multipart)
+            .await
+    }
+    {%- endif %}
 }
 
 async fn load_item(ctx: &AppContext, id: i32) -> Result<Model> {
@@ -56,10 +83,20 @@ async fn load_item(ctx: &AppContext, id: i32) -> Result<Model> {
 }
 
 #[debug_handler]
+{% if with_tracing -%}
+#[tracing::instrument(
+    name = "{{plural_file_name}}.list",
+    skip_all,
+    fields(model = "{{file_name}}", action = "list", db.query_count = tracing::field::Empty)
+)]
+{% endif -%}
 pub async fn list(
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
 ) -> Result<Response> {
+    {%- if with_tracing %}
+    let _queries = loco_rs::db::QueryCount::start();
+    {%- endif %}
     let item = Entity::find()
         .order_by(Column::Id, Order::Desc)
         .all(&ctx.db)
@@ -68,73 +105,189 @@ pub async fn list(
 }
 
 #[debug_handler]
+{% if with_tracing -%}
+#[tracing::instrument(
+    name = "{{plural_file_name}}.new",
+    skip_all,
+    fields(model = "{{file_name}}", action = "new", db.query_count = tracing::field::Empty)
+)]
+{% endif -%}
 pub async fn new(
     ViewEngine(v): ViewEngine<TeraView>,
     State(_ctx): State<AppContext>,
 ) -> Result<Response> {
+    {%- if with_tracing %}
+    let _queries = loco_rs::db::QueryCount::start();
+    {%- endif %}
     views::{{file_name}}::create(&v)
 }
 
 #[debug_handler]
+{% if with_tracing -%}
+#[tracing::instrument(
+    name = "{{plural_file_name}}.update",
+    skip_all,
+    fields(model = "{{file_name}}", action = "update", db.query_count = tracing::field::Empty)
+)]
+{% endif -%}
 pub async fn update(
     Path(id): Path<i32>,
+    ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
+    {% if with_files -%}
+    multipart: Multipart,
+) -> Result<Response> {
+    let params = Params::from_multipart(&ctx, multipart).await?;
+    {%- else -%}
     Json(params): Json<Params>,
 ) -> Result<Response> {
+    {%- endif %}
+    {%- if with_tracing %}
+    let _queries = loco_rs::db::QueryCount::start();
+    {%- endif %}
     let item = load_item(&ctx, id).await?;
     let mut item = item.into_active_model();
     params.update(&mut item);
-    let _ = item.update(&ctx.db).await?;
-    format::render().redirect_with_header_key("HX-Redirect", "/{{name | plural}}")
+    let item = item.update(&ctx.db).await?;
+    views::{{file_name}}::row(&v, &item)
 }
 
 #[debug_handler]
+{% if with_tracing -%}
+#[tracing::instrument(
+    name = "{{plural_file_name}}.edit",
+    skip_all,
+    fields(model = "{{file_name}}", action = "edit", db.query_count = tracing::field::Empty)
+)]
+{% endif -%}
 pub async fn edit(
     Path(id): Path<i32>,
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
 ) -> Result<Response> {
+    {%- if with_tracing %}
+    let _queries = loco_rs::db::QueryCount::start();
+    {%- endif %}
     let item = load_item(&ctx, id).await?;
     views::{{file_name}}::edit(&v, &item)
 }
 
 #[debug_handler]
+{% if with_tracing -%}
+#[tracing::instrument(
+    name = "{{plural_file_name}}.row",
+    skip_all,
+    fields(model = "{{file_name}}", action = "row", db.query_count = tracing::field::Empty)
+)]
+{% endif -%}
+pub async fn row(
+    Path(id): Path<i32>,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    {%- if with_tracing %}
+    let _queries = loco_rs::db::QueryCount::start();
+    {%- endif %}
+    let item = load_item(&ctx, id).await?;
+    views::{{file_name}}::row(&v, &item)
+}
+
+#[debug_handler]
+{% if with_tracing -%}
+#[tracing::instrument(
+    name = "{{plural_file_name}}.edit_row",
+    skip_all,
+    fields(model = "{{file_name}}", action = "edit_row", db.query_count = tracing::field::Empty)
+)]
+{% endif -%}
+pub async fn edit_row(
+    Path(id): Path<i32>,
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    {%- if with_tracing %}
+    let _queries = loco_rs::db::QueryCount::start();
+    {%- endif %}
+    let item = load_item(&ctx, id).await?;
+    views::{{file_name}}::edit_row(&v, &item)
+}
+
+#[debug_handler]
+{% if with_tracing -%}
+#[tracing::instrument(
+    name = "{{plural_file_name}}.show",
+    skip_all,
+    fields(model = "{{file_name}}", action = "show", db.query_count = tracing::field::Empty)
+)]
+{% endif -%}
 pub async fn show(
     Path(id): Path<i32>,
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
 ) -> Result<Response> {
+    {%- if with_tracing %}
+    let _queries = loco_rs::db::QueryCount::start();
+    {%- endif %}
     let item = load_item(&ctx, id).await?;
     views::{{file_name}}::show(&v, &item)
 }
 
 #[debug_handler]
+{% if with_tracing -%}
+#[tracing::instrument(
+    name = "{{plural_file_name}}.add",
+    skip_all,
+    fields(model = "{{file_name}}", action = "add", db.query_count = tracing::field::Empty)
+)]
+{% endif -%}
 pub async fn add(
+    ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
+    {% if with_files -%}
+    multipart: Multipart,
+) -> Result<Response> {
+    let params = Params::from_multipart(&ctx, multipart).await?;
+    {%- else -%}
     Json(params): Json<Params>,
 ) -> Result<Response> {
+    {%- endif %}
+    {%- if with_tracing %}
+    let _queries = loco_rs::db::QueryCount::start();
+    {%- endif %}
     let mut item = ActiveModel {
         ..Default::default()
     };
     params.update(&mut item);
-    let _ = item.insert(&ctx.db).await?;
-    format::render().redirect_with_header_key("HX-Redirect", "/{{name | plural}}")
+    let item = item.insert(&ctx.db).await?;
+    views::{{file_name}}::row(&v, &item)
 }
 
 #[debug_handler]
+{% if with_tracing -%}
+#[tracing::instrument(
+    name = "{{plural_file_name}}.remove",
+    skip_all,
+    fields(model = "{{file_name}}", action = "remove", db.query_count = tracing::field::Empty)
+)]
+{% endif -%}
 pub async fn remove(Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
+    {%- if with_tracing %}
+    let _queries = loco_rs::db::QueryCount::start();
+    {%- endif %}
     load_item(&ctx, id).await?.delete(&ctx.db).await?;
     format::empty()
 }
 
 pub fn routes() -> Routes {
     Routes::new()
-        .prefix("{{file_name | plural}}/")
+        .prefix("{{plural_file_name}}/")
         .add("/", get(list))
         .add("/", post(add))
         .add("new", get(new))
         .add("{id}", get(show))
         .add("{id}/edit", get(edit))
+        .add("{id}/row", get(row))
+        .add("{id}/edit_row", get(edit_row))
         .add("{id}", delete(remove))
         .add("{id}", put(update))
         .add("{id}", patch(update))
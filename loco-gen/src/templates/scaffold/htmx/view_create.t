@@ -17,7 +17,7 @@ Create new {{name}}
 {% raw %}{% block content %}{% endraw %}
 <div class="mb-10">
     <div id="error-message" class="mt-4 text-sm text-red-600"></div>
-    <form hx-post="/{{name | plural}}" hx-ext="submitjson" class="flex-1 lg:max-w-2xl">
+    <form hx-post="/{{plural_name}}" {% if file_columns | length > 0 %}hx-encoding="multipart/form-data"{% else %}hx-ext="submitjson"{% endif %} hx-target="#{{file_name}}-created" hx-swap="afterbegin" hx-on::after-request="if (event.detail.successful) this.reset()" class="flex-1 lg:max-w-2xl">
         {% for column in columns -%}
             {{ render_form_field(fname=column.0, rust_type=column.1, ftype=column.2)}}
         {% endfor -%}
@@ -26,6 +26,11 @@ Create new {{name}}
         </div>
 
     </form>
+    <table class="mt-5 w-full caption-bottom text-sm">
+        <tbody id="{{file_name}}-created"></tbody>
+    </table>
+    <br />
+    <a href="/{{plural_name}}">Back to {{plural_name}}</a>
 </div>
 {% raw %}{% endblock content %}{% endraw %}
 
@@ -17,7 +17,7 @@ Edit {{name}}: {% raw %}{{ item.id }}{% endraw %}
 {% raw %}{% block content %}{% endraw %}
 <div class="mb-10">
     <div id="error-message" class="mt-4 text-sm text-red-600"></div>
-    <form hx-put="/{{name | plural}}/{% raw %}{{ item.id }}{% endraw %}" hx-ext="submitjson" hx-target="#success-message" class="flex-1 lg:max-w-2xl">
+    <form hx-put="/{{plural_name}}/{% raw %}{{ item.id }}{% endraw %}" {% if file_columns | length > 0 %}hx-encoding="multipart/form-data"{% else %}hx-ext="submitjson"{% endif %} hx-target="#{{file_name}}-{% raw %}{{ item.id }}{% endraw %}" hx-swap="outerHTML" class="flex-1 lg:max-w-2xl">
         {% for column in columns -%}
             {{ render_form_field(fname=column.0, rust_type=column.1, ftype=column.2, edit_form=true)}}
         {% endfor -%}
@@ -25,13 +25,17 @@ Edit {{name}}: {% raw %}{{ item.id }}{% endraw %}
             <div class="mt-5">
                 <button class=" text-xs py-3 px-6 rounded-lg bg-gray-900 text-white" type="submit">Submit</button>
                 <button class="text-xs py-3 px-6 rounded-lg bg-red-600 text-white"
-                            onclick="confirmDelete(event, '/{{name | plural}}/{% raw %}{{ item.id }}{% endraw %}', '/{{name | plural}}' )">Delete</button>
+                            onclick="confirmDelete(event, '/{{plural_name}}/{% raw %}{{ item.id }}{% endraw %}', '/{{plural_name}}' )">Delete</button>
             </div>
         </div>
     </form>
-    <div id="success-message" class="mt-4"></div>
+    <table class="mt-5 w-full caption-bottom text-sm">
+        <tbody>
+            {% raw %}{% include "{% endraw %}{{file_name}}{% raw %}/_row.html" %}{% endraw %}
+        </tbody>
+    </table>
     <br />
-    <a href="/{{name | plural}}">Back to {{name}}</a>
+    <a href="/{{plural_name}}">Back to {{name}}</a>
 </div>
 {% raw %}{% endblock content %}{% endraw %}
 
@@ -36,17 +36,7 @@ List of {{file_name}}
                 </thead>
                 <tbody class="[&amp;_tr:last-child]:border-0">
                    {% raw %}{% for item in items %}{% endraw %}
-                    <tr class="border-b transition-colors hover:bg-muted/50">
-                        {% for column in columns -%}
-                          <td
-                            class="p-2 align-middle  font-medium">
-                            {% raw %}{{item.{% endraw %}{{column.0}}{% raw %} | escape }}{% endraw %}
-                        </td>
-                        {% endfor -%}
-                        <td>
-                            <a href="/{{name | plural}}/{% raw %}{{ item.id }}{% endraw %}/edit">Edit</a>
-                        </td>
-                    </tr>
+                    {% raw %}{% include "{% endraw %}{{file_name}}{% raw %}/_row.html" %}{% endraw %}
                     {% raw %}{% endfor %}{% endraw %}
                 </tbody>
             </table>
@@ -54,7 +44,7 @@ List of {{file_name}}
     
         <div class="flex">
             <div class="ml-auto  p-4">
-                <a href="/{{name | plural}}/new"
+                <a href="/{{plural_name}}/new"
                     class="mt-5 bg-blue-500 text-white bg-primary-600 hover:bg-primary-700 focus:ring-4 focus:outline-none focus:ring-primary-300 font-medium rounded-lg text-sm px-5 py-2.5 text-center dark:bg-primary-600 dark:hover:bg-primary-700 dark:focus:ring-primary-800">
                     Create
                 </a>
@@ -68,7 +58,7 @@ List of {{file_name}}
         <div class="bg-white rounded-lg shadow-lg p-8 max-w-4xl w-full flex flex-col items-center">
             <h3 class="font-bold text-lg">Nothing Here Yet</h3>
             There are no records to display. Add a new record to get started!
-            <a href="/{{name | plural}}/new"
+            <a href="/{{plural_name}}/new"
             class="mt-5 bg-blue-500 text-white bg-primary-600 hover:bg-primary-700 focus:ring-4 focus:outline-none focus:ring-primary-300 font-medium rounded-lg text-sm px-5 py-2.5 text-center dark:bg-primary-600 dark:hover:bg-primary-700 dark:focus:ring-primary-800">
             Create
         </a>
@@ -0,0 +1,17 @@
+{% set file_name = name |  snake_case -%}
+{% set module_name = file_name | pascal_case -%}
+to: assets/views/{{file_name}}/_row.html
+skip_exists: true
+message: "{{file_name}} row partial was added successfully."
+---
+<tr id="{{file_name}}-{% raw %}{{ item.id }}{% endraw %}" class="border-b transition-colors hover:bg-muted/50">
+    {% for column in columns -%}
+    <td class="p-2 align-middle font-medium">
+        {% raw %}{{item.{% endraw %}{{column.0}}{% raw %} | escape }}{% endraw %}
+    </td>
+    {% endfor -%}
+    <td class="p-2 align-middle">
+        <button hx-get="/{{plural_name}}/{% raw %}{{ item.id }}{% endraw %}/edit_row" hx-target="closest tr" hx-swap="outerHTML">Edit</button>
+        <button hx-delete="/{{plural_name}}/{% raw %}{{ item.id }}{% endraw %}" hx-confirm="Are you sure you want to delete this item?" hx-target="closest tr" hx-swap="outerHTML" class="text-red-600">Delete</button>
+    </td>
+</tr>
@@ -0,0 +1,17 @@
+{% set file_name = name |  snake_case -%}
+{% set module_name = file_name | pascal_case -%}
+to: assets/views/{{file_name}}/_row_edit.html
+skip_exists: true
+message: "{{file_name}} inline edit partial was added successfully."
+---
+<tr id="{{file_name}}-{% raw %}{{ item.id }}{% endraw %}" hx-put="/{{plural_name}}/{% raw %}{{ item.id }}{% endraw %}" {% if file_columns | length > 0 %}hx-encoding="multipart/form-data"{% else %}hx-ext="submitjson"{% endif %} hx-include="this" hx-trigger="save, keyup[key=='Enter']" hx-target="this" hx-swap="outerHTML" class="border-b bg-muted/50">
+    {% for column in columns -%}
+    <td class="p-2 align-middle">
+        {{ render_form_field(fname=column.0, rust_type=column.1, ftype=column.2, edit_form=true)}}
+    </td>
+    {% endfor -%}
+    <td class="p-2 align-middle">
+        <button onclick="htmx.trigger(this.closest('tr'), 'save')">Save</button>
+        <button hx-get="/{{plural_name}}/{% raw %}{{ item.id }}{% endraw %}/row" hx-target="closest tr" hx-swap="outerHTML">Cancel</button>
+    </td>
+</tr>
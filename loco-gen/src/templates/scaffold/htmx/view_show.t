@@ -23,6 +23,6 @@ View {{name}}: {% raw %}{{ item.id }}{% endraw %}
     </div>
 {% endfor -%}
 <br />
-<a href="/{{name | plural}}">Back to {{name | plural}}</a>
+<a href="/{{plural_name}}">Back to {{plural_name}}</a>
 </div>
 {% raw %}{% endblock content %}{% endraw %}
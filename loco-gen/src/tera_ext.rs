@@ -53,6 +53,20 @@ impl tera::Function for FormField {
         let required_value = if is_required { "required" } else { "" };
 
         let element = match rust_type {
+            _ if ftype == "file" => {
+                let input = format!(
+                    r#"<input class="{input_class}" id="{fname}" name="{fname}" type="file" {required_value} />"#,
+                );
+                if is_edit_form {
+                    let desc = input_description(format!("current file: {value}"));
+                    format!(
+                        r"{input}
+    {desc}",
+                    )
+                } else {
+                    input
+                }
+            }
             "Uuid" | "Option<Uuid>" => {
                 let desc = input_description("e.g: 11111111-1111-1111-1111-111111111111.");
                 let input = input_string(
@@ -152,6 +166,11 @@ impl tera::Function for FormField {
                     r#"<input class="{input_class}" id="{fname}" name="{fname}" type="date" value="{value}" {required_value} />"#,
                 )
             }
+            "Time" | "Option<Time>" => {
+                format!(
+                    r#"<input class="{input_class}" id="{fname}" name="{fname}" type="time" step="1" value="{value}" {required_value} />"#,
+                )
+            }
             "bool" | "Option<bool>" => {
                 let checked = if is_edit_form {
                     format!("{{% if item.{fname} %}}checked{{%endif %}}")
@@ -64,6 +64,10 @@ fn test_migrations_flow(#[values("postgres", "sqlite")] db_kind: &str) {
     // push arity arguments manually
     type_names.push("age:decimal_len:8:24".to_string());
     type_names.push("age_nonull:decimal_len!:8:24".to_string());
+    type_names.push("code:string_len:8".to_string());
+    type_names.push("code_nonull:string_len!:8".to_string());
+    type_names.push("grade:char_len:2".to_string());
+    type_names.push("grade_nonull:char_len!:2".to_string());
 
     if db_kind == "postgres" {
         type_names.push("array_string:array:string".to_string());
@@ -1,6 +1,8 @@
 use super::utils::APP_ROUTS;
 use insta::assert_snapshot;
-use loco_gen::{collect_messages, generate, AppInfo, Component, ScaffoldKind};
+use loco_gen::{
+    collect_messages, generate, preview_in, AppInfo, Component, GenerateOptions, ScaffoldKind,
+};
 use rrgen::RRgen;
 use rstest::rstest;
 use std::fs;
@@ -86,3 +88,156 @@ fn can_generate(#[case] kind: ScaffoldKind) {
         }
     }
 }
+
+#[test]
+fn can_register_routes_once() {
+    let component = || Component::Controller {
+        name: "movie".to_string(),
+        actions: vec!["GET".to_string()],
+        kind: ScaffoldKind::Api,
+    };
+
+    let tree_fs = tree_fs::TreeBuilder::default()
+        .drop(true)
+        .add_empty("src/controllers/mod.rs")
+        .add_empty("tests/requests/mod.rs")
+        .add("src/app.rs", APP_ROUTS)
+        .create()
+        .unwrap();
+
+    let rrgen = RRgen::with_working_dir(&tree_fs.root);
+    let app_info = AppInfo {
+        app_name: "tester".to_string(),
+    };
+
+    generate(&rrgen, component(), &app_info).expect("Generation failed");
+    // the controller file is gone but its registrations are still in place
+    fs::remove_file(tree_fs.root.join("src/controllers/movie.rs")).unwrap();
+    generate(&rrgen, component(), &app_info).expect("Generation failed");
+
+    let app_rs = fs::read_to_string(tree_fs.root.join("src/app.rs")).unwrap();
+    assert_eq!(
+        app_rs
+            .matches(".add_route(controllers::movie::routes())")
+            .count(),
+        1
+    );
+    assert!(!app_rs.contains("TODO"));
+    let mod_rs = fs::read_to_string(tree_fs.root.join("src/controllers/mod.rs")).unwrap();
+    assert_eq!(mod_rs.matches("pub mod movie;").count(), 1);
+}
+
+#[test]
+fn can_fall_back_to_todo_when_routes_not_found() {
+    let component = Component::Controller {
+        name: "movie".to_string(),
+        actions: vec!["GET".to_string()],
+        kind: ScaffoldKind::Api,
+    };
+
+    let mut settings = insta::Settings::clone_current();
+    settings.set_prepend_module_to_snapshot(false);
+    settings.set_snapshot_suffix("Api_controller");
+    let _guard = settings.bind_to_scope();
+
+    let tree_fs = tree_fs::TreeBuilder::default()
+        .drop(true)
+        .add_empty("src/controllers/mod.rs")
+        .add_empty("tests/requests/mod.rs")
+        .add("src/app.rs", "impl Hooks for App {}\n")
+        .create()
+        .unwrap();
+
+    let rrgen = RRgen::with_working_dir(&tree_fs.root);
+
+    generate(
+        &rrgen,
+        component,
+        &AppInfo {
+            app_name: "tester".to_string(),
+        },
+    )
+    .expect("Generation failed");
+
+    assert_snapshot!(
+        "inject[app_rs_without_app_routes]",
+        fs::read_to_string(tree_fs.root.join("src/app.rs")).unwrap()
+    );
+}
+
+#[test]
+fn can_preview_in_project_without_writing() {
+    let component = Component::Controller {
+        name: "movie".to_string(),
+        actions: vec!["GET".to_string()],
+        kind: ScaffoldKind::Api,
+    };
+
+    let tree_fs = tree_fs::TreeBuilder::default()
+        .drop(true)
+        .add("src/controllers/mod.rs", "pub mod movie;\n")
+        .add("tests/requests/movie.rs", "// existing test\n")
+        .add_empty("tests/requests/mod.rs")
+        .add("src/app.rs", APP_ROUTS)
+        .create()
+        .unwrap();
+
+    let files = preview_in(
+        &tree_fs.root,
+        component,
+        &AppInfo {
+            app_name: "tester".to_string(),
+        },
+        &GenerateOptions::default(),
+    )
+    .expect("Preview failed");
+
+    // the existing test is skipped, and so is its injection
+    assert_eq!(
+        files
+            .iter()
+            .map(|file| file.path.as_str())
+            .collect::<Vec<_>>(),
+        vec!["src/controllers/movie.rs"]
+    );
+    // the module is already declared, and once the routes are registered the
+    // fallback TODO is skipped
+    assert_eq!(files[0].injections, vec!["src/app.rs"]);
+
+    assert!(!tree_fs.root.join("src/controllers/movie.rs").exists());
+    assert_eq!(
+        fs::read_to_string(tree_fs.root.join("src/app.rs")).unwrap(),
+        APP_ROUTS
+    );
+    assert_eq!(
+        fs::read_to_string(tree_fs.root.join("tests/requests/movie.rs")).unwrap(),
+        "// existing test\n"
+    );
+}
+
+#[test]
+fn cannot_preview_injection_into_missing_file() {
+    let component = Component::Controller {
+        name: "movie".to_string(),
+        actions: vec!["GET".to_string()],
+        kind: ScaffoldKind::Api,
+    };
+
+    let tree_fs = tree_fs::TreeBuilder::default()
+        .drop(true)
+        .add_empty("src/controllers/mod.rs")
+        .add_empty("tests/requests/mod.rs")
+        .create()
+        .unwrap();
+
+    let err = preview_in(
+        &tree_fs.root,
+        component,
+        &AppInfo {
+            app_name: "tester".to_string(),
+        },
+        &GenerateOptions::default(),
+    )
+    .expect_err("src/app.rs is missing");
+    assert!(err.to_string().contains("cannot inject into src/app.rs"));
+}
@@ -1,8 +1,12 @@
 use super::utils::{guess_file_by_time, MIGRATION_SRC_LIB};
 use insta::{assert_snapshot, with_settings};
-use loco_gen::{collect_messages, generate, AppInfo, Component};
+use loco_gen::{
+    collect_messages, generate, generate_with_options, inflection::Inflections, preview,
+    template::BuiltinTemplateSource, AppInfo, Component, GenerateOptions, RenderedFile,
+};
 use rrgen::RRgen;
-use std::fs;
+use serial_test::serial;
+use std::{collections::BTreeMap, fs, path::Path};
 
 macro_rules! configure_insta {
     () => {
@@ -137,3 +141,187 @@ fn fail_when_test_models_mod_not_exists() {
         "cannot inject into tests/models/mod.rs: file does not exist"
     );
 }
+
+#[test]
+#[serial]
+fn can_generate_with_custom_inflections() {
+    std::env::set_var("SKIP_MIGRATION", "");
+    let tree_fs = tree_fs::TreeBuilder::default()
+        .drop(true)
+        .add("migration/src/lib.rs", MIGRATION_SRC_LIB)
+        .add_empty("tests/models/mod.rs")
+        .create()
+        .unwrap();
+
+    let options = GenerateOptions {
+        inflections: Inflections {
+            irregular: BTreeMap::from([("person".to_string(), "persons".to_string())]),
+            uncountable: vec![],
+        },
+        ..Default::default()
+    };
+
+    let rrgen = RRgen::with_working_dir(&tree_fs.root);
+    let component = Component::Model {
+        name: "person".to_string(),
+        with_tz: true,
+        fields: vec![("name".to_string(), "string".to_string())],
+    };
+
+    generate_with_options(
+        &rrgen,
+        component,
+        &AppInfo {
+            app_name: "tester".to_string(),
+        },
+        &options,
+    )
+    .expect("Generation failed");
+
+    let migration_path = tree_fs.root.join("migration/src");
+    let migration_file = guess_file_by_time(&migration_path, "m{TIME}_persons.rs", 3)
+        .expect("Failed to find the generated migration file");
+    let content = fs::read_to_string(migration_file).expect("Failed to read the migration file");
+    assert!(content.contains(r#"create_table(m, "persons","#));
+    assert!(content.contains(r#"drop_table(m, "persons")"#));
+}
+
+#[test]
+#[serial]
+fn can_generate_reversible_migration_with_references() {
+    std::env::set_var("SKIP_MIGRATION", "");
+    let tree_fs = tree_fs::TreeBuilder::default()
+        .drop(true)
+        .add("migration/src/lib.rs", MIGRATION_SRC_LIB)
+        .add_empty("tests/models/mod.rs")
+        .create()
+        .unwrap();
+
+    let rrgen = RRgen::with_working_dir(&tree_fs.root);
+    let component = Component::Model {
+        name: "post".to_string(),
+        with_tz: true,
+        fields: vec![
+            ("title".to_string(), "string".to_string()),
+            ("user".to_string(), "references".to_string()),
+        ],
+    };
+
+    generate(
+        &rrgen,
+        component,
+        &AppInfo {
+            app_name: "tester".to_string(),
+        },
+    )
+    .expect("Generation failed");
+
+    let migration_path = tree_fs.root.join("migration/src");
+    let migration_file = guess_file_by_time(&migration_path, "m{TIME}_posts.rs", 3)
+        .expect("Failed to find the generated migration file");
+    let content = fs::read_to_string(migration_file).expect("Failed to read the migration file");
+
+    let (up, down) = content
+        .split_once("async fn down")
+        .expect("migration has no down");
+    assert!(up.contains(r#"create_table(m, "posts","#));
+    assert!(up.contains(r#"("title", ColType::StringNull),"#));
+    assert!(up.contains(r#"("user", ""),"#));
+    // dropping the table removes its foreign keys as well
+    assert!(down.contains(r#"drop_table(m, "posts")"#));
+}
+
+#[test]
+#[serial]
+fn can_generate_migration_with_default_values() {
+    std::env::set_var("SKIP_MIGRATION", "");
+    let tree_fs = tree_fs::TreeBuilder::default()
+        .drop(true)
+        .add("migration/src/lib.rs", MIGRATION_SRC_LIB)
+        .add_empty("tests/models/mod.rs")
+        .create()
+        .unwrap();
+
+    let rrgen = RRgen::with_working_dir(&tree_fs.root);
+    let component = Component::Model {
+        name: "post".to_string(),
+        with_tz: true,
+        fields: vec![
+            ("status".to_string(), "string!:default:draft".to_string()),
+            ("views".to_string(), "int!:default:0".to_string()),
+        ],
+    };
+
+    generate(
+        &rrgen,
+        component,
+        &AppInfo {
+            app_name: "tester".to_string(),
+        },
+    )
+    .expect("Generation failed");
+
+    let migration_path = tree_fs.root.join("migration/src");
+    let migration_file = guess_file_by_time(&migration_path, "m{TIME}_posts.rs", 3)
+        .expect("Failed to find the generated migration file");
+    let content = fs::read_to_string(migration_file).expect("Failed to read the migration file");
+
+    assert!(content.contains(r#"("status", ColType::StringWithDefault("draft".to_string())),"#));
+    assert!(content.contains(r#"("views", ColType::IntegerWithDefault(0)),"#));
+}
+
+#[test]
+#[serial]
+fn can_preview_model_without_project() {
+    std::env::set_var("SKIP_MIGRATION", "");
+    let tree_fs = tree_fs::TreeBuilder::default()
+        .drop(true)
+        .add("migration/src/lib.rs", MIGRATION_SRC_LIB)
+        .add_empty("tests/models/mod.rs")
+        .create()
+        .unwrap();
+
+    let component = || Component::Model {
+        name: "movie".to_string(),
+        with_tz: true,
+        fields: vec![
+            ("title".to_string(), "string".to_string()),
+            ("user".to_string(), "references".to_string()),
+        ],
+    };
+    let appinfo = AppInfo {
+        app_name: "tester".to_string(),
+    };
+
+    let options = GenerateOptions {
+        templates: Box::new(BuiltinTemplateSource),
+        ..Default::default()
+    };
+    let files = preview(component(), &appinfo, &options).expect("Preview failed");
+    let rrgen = RRgen::with_working_dir(&tree_fs.root);
+    generate(&rrgen, component(), &appinfo).expect("Generation failed");
+
+    let migration = files
+        .iter()
+        .find(|file| file.template == Path::new("model/model.t"))
+        .expect("migration missing from preview");
+    assert!(migration.path.starts_with("migration/src/m"));
+    assert!(migration.path.ends_with("_movies.rs"));
+    assert!(migration
+        .content
+        .contains(r#"("title", ColType::StringNull),"#));
+    assert_eq!(migration.injections, vec!["migration/src/lib.rs"; 2]);
+
+    // apart from the migration timestamp, the preview is what generation writes
+    for RenderedFile { path, content, .. } in &files {
+        let written = if *path == migration.path {
+            let generated =
+                guess_file_by_time(&tree_fs.root.join("migration/src"), "m{TIME}_movies.rs", 3)
+                    .expect("Failed to find the generated migration file");
+            fs::read_to_string(generated)
+        } else {
+            fs::read_to_string(tree_fs.root.join(path))
+        };
+        assert_eq!(&written.expect("generated file missing"), content, "{path}");
+    }
+}
@@ -25,6 +25,7 @@ fn can_generate(#[case] kind: ScaffoldKind) {
             ("user".to_string(), "references".to_string()),
         ],
         kind: kind.clone(),
+        with_tracing: false,
     };
 
     let tree_fs = tree_fs::TreeBuilder::default()
@@ -109,7 +110,13 @@ fn can_generate(#[case] kind: ScaffoldKind) {
 
     // VIEWS
     match kind {
-        ScaffoldKind::Api => (),
+        ScaffoldKind::Api => {
+            assert_snapshot!(
+                "generate[test_request]",
+                fs::read_to_string(tree_fs.root.join("tests/requests/movie.rs"))
+                    .expect("Failed to read movie.rs")
+            );
+        }
         ScaffoldKind::Html | ScaffoldKind::Htmx => {
             let base_views_path = tree_fs.root.join("src").join("views");
             assert_snapshot!(
@@ -123,7 +130,10 @@ fn can_generate(#[case] kind: ScaffoldKind) {
             );
 
             let views_path = tree_fs.root.join("assets").join("views").join("movie");
-            let views = vec!["create", "edit", "list", "show"];
+            let mut views = vec!["create", "edit", "list", "show"];
+            if matches!(kind, ScaffoldKind::Htmx) {
+                views.extend(["_row", "_row_edit"]);
+            }
             for view in views {
                 assert_snapshot!(
                     format!("generate[views_[{view}]]"),
@@ -136,3 +146,202 @@ fn can_generate(#[case] kind: ScaffoldKind) {
 }
 
 // thread 'templates::scaffold::can_generate::case_1' panicked at loco-gen/tests/templates/scaffold.rs:48:6:
+
+#[rstest]
+#[case(ScaffoldKind::Api)]
+#[case(ScaffoldKind::Html)]
+#[case(ScaffoldKind::Htmx)]
+#[test]
+fn can_generate_with_file_field(#[case] kind: ScaffoldKind) {
+    std::env::set_var("SKIP_MIGRATION", "");
+    let mut settings = insta::Settings::clone_current();
+    settings.set_prepend_module_to_snapshot(false);
+    settings.set_snapshot_suffix(format!("{kind:?}_file_scaffold"));
+    let _guard = settings.bind_to_scope();
+
+    let component = Component::Scaffold {
+        name: "profile".to_string(),
+        with_tz: true,
+        fields: vec![
+            ("name".to_string(), "string".to_string()),
+            ("age".to_string(), "int".to_string()),
+            ("avatar".to_string(), "file".to_string()),
+        ],
+        kind: kind.clone(),
+        with_tracing: false,
+    };
+
+    let tree_fs = tree_fs::TreeBuilder::default()
+        .drop(true)
+        .add_empty("src/controllers/mod.rs")
+        .add_empty("tests/models/mod.rs")
+        .add_empty("src/views/mod.rs")
+        .add_empty("tests/requests/mod.rs")
+        .add("migration/src/lib.rs", MIGRATION_SRC_LIB)
+        .add("src/app.rs", APP_ROUTS)
+        .create()
+        .unwrap();
+
+    let rrgen = RRgen::with_working_dir(&tree_fs.root).add_template_engine(tera_ext::new());
+
+    generate(
+        &rrgen,
+        component,
+        &AppInfo {
+            app_name: "tester".to_string(),
+        },
+    )
+    .expect("Generation failed");
+
+    // the file is persisted as a nullable string column holding the storage key
+    let migration_path = tree_fs.root.join("migration/src");
+    let migration_file = guess_file_by_time(&migration_path, "m{TIME}_profiles.rs", 3)
+        .expect("Failed to find the generated migration file");
+    let migration_content =
+        fs::read_to_string(&migration_file).expect("Failed to read the migration file");
+    assert!(migration_content.contains(r#"("avatar", ColType::StringNull)"#));
+
+    let controllers_path = tree_fs.root.join("src").join("controllers");
+    assert_snapshot!(
+        "generate[controller_file]",
+        fs::read_to_string(controllers_path.join("profile.rs")).expect("controller file missing")
+    );
+
+    match kind {
+        ScaffoldKind::Api => (),
+        ScaffoldKind::Html | ScaffoldKind::Htmx => {
+            let views_path = tree_fs.root.join("assets").join("views").join("profile");
+            let mut views = vec!["create", "edit"];
+            if matches!(kind, ScaffoldKind::Htmx) {
+                views.push("_row_edit");
+            }
+            for view in views {
+                assert_snapshot!(
+                    format!("generate[views_[{view}]]"),
+                    fs::read_to_string(views_path.join(format!("{view}.html")))
+                        .expect("view file missing")
+                );
+            }
+        }
+    }
+}
+
+#[rstest]
+#[case(ScaffoldKind::Api)]
+#[case(ScaffoldKind::Html)]
+#[case(ScaffoldKind::Htmx)]
+#[test]
+fn can_generate_with_tracing(#[case] kind: ScaffoldKind) {
+    std::env::set_var("SKIP_MIGRATION", "");
+    let mut settings = insta::Settings::clone_current();
+    settings.set_prepend_module_to_snapshot(false);
+    settings.set_snapshot_suffix(format!("{kind:?}_tracing_scaffold"));
+    let _guard = settings.bind_to_scope();
+
+    let component = Component::Scaffold {
+        name: "movie".to_string(),
+        with_tz: true,
+        fields: vec![("title".to_string(), "string".to_string())],
+        kind: kind.clone(),
+        with_tracing: true,
+    };
+
+    let tree_fs = tree_fs::TreeBuilder::default()
+        .drop(true)
+        .add_empty("src/controllers/mod.rs")
+        .add_empty("tests/models/mod.rs")
+        .add_empty("src/views/mod.rs")
+        .add_empty("tests/requests/mod.rs")
+        .add("migration/src/lib.rs", MIGRATION_SRC_LIB)
+        .add("src/app.rs", APP_ROUTS)
+        .create()
+        .unwrap();
+
+    let rrgen = RRgen::with_working_dir(&tree_fs.root).add_template_engine(tera_ext::new());
+
+    generate(
+        &rrgen,
+        component,
+        &AppInfo {
+            app_name: "tester".to_string(),
+        },
+    )
+    .expect("Generation failed");
+
+    let controller = fs::read_to_string(tree_fs.root.join("src/controllers/movie.rs"))
+        .expect("controller file missing");
+    assert!(controller.contains(r#"name = "movies.list","#));
+    assert!(controller.contains(
+        r#"fields(model = "movie", action = "update", db.query_count = tracing::field::Empty)"#
+    ));
+    assert_eq!(
+        controller.matches("#[tracing::instrument(").count(),
+        controller.matches("#[debug_handler]").count()
+    );
+    assert_eq!(
+        controller
+            .matches("let _queries = loco_rs::db::QueryCount::start();")
+            .count(),
+        controller.matches("#[debug_handler]").count()
+    );
+
+    assert_snapshot!("generate[controller_file]", controller);
+}
+
+#[test]
+fn can_generate_with_binary_field() {
+    std::env::set_var("SKIP_MIGRATION", "");
+    let mut settings = insta::Settings::clone_current();
+    settings.set_prepend_module_to_snapshot(false);
+    settings.set_snapshot_suffix("Api_binary_scaffold");
+    let _guard = settings.bind_to_scope();
+
+    let component = Component::Scaffold {
+        name: "photo".to_string(),
+        with_tz: true,
+        fields: vec![
+            ("title".to_string(), "string".to_string()),
+            ("thumbnail".to_string(), "binary".to_string()),
+            ("content".to_string(), "binary!".to_string()),
+        ],
+        kind: ScaffoldKind::Api,
+        with_tracing: false,
+    };
+
+    let tree_fs = tree_fs::TreeBuilder::default()
+        .drop(true)
+        .add_empty("src/controllers/mod.rs")
+        .add_empty("tests/models/mod.rs")
+        .add_empty("src/views/mod.rs")
+        .add_empty("tests/requests/mod.rs")
+        .add("migration/src/lib.rs", MIGRATION_SRC_LIB)
+        .add("src/app.rs", APP_ROUTS)
+        .create()
+        .unwrap();
+
+    let rrgen = RRgen::with_working_dir(&tree_fs.root).add_template_engine(tera_ext::new());
+
+    generate(
+        &rrgen,
+        component,
+        &AppInfo {
+            app_name: "tester".to_string(),
+        },
+    )
+    .expect("Generation failed");
+
+    let migration_path = tree_fs.root.join("migration/src");
+    let migration_file = guess_file_by_time(&migration_path, "m{TIME}_photos.rs", 3)
+        .expect("Failed to find the generated migration file");
+    let migration_content =
+        fs::read_to_string(&migration_file).expect("Failed to read the migration file");
+    assert!(migration_content.contains(r#"("thumbnail", ColType::BlobNull)"#));
+    assert!(migration_content.contains(r#"("content", ColType::Blob)"#));
+
+    let controller = fs::read_to_string(tree_fs.root.join("src/controllers/photo.rs"))
+        .expect("controller file missing");
+    assert!(controller.contains("pub thumbnail: Option<Vec<u8>>,"));
+    assert!(controller.contains("pub content: Vec<u8>,"));
+
+    assert_snapshot!("generate[controller_file]", controller);
+}
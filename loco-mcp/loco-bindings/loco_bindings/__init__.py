@@ -6,7 +6,17 @@ It exposes four main functions:
 - generate_model: Generate a Loco model with migrations
 - generate_scaffold: Generate a full scaffold (model + controller + views)
 - generate_controller_view: Generate a controller with views
+- generate_migration: Generate a standalone migration
+- generate_alter_migration: Generate a migration applying a model diff
+- generate_seed: Write a seed data file for a model
+- render_model_preview: Render a model without a project
 - create_project: Create a new Loco project from templates
+- validate_generation: Check a model spec without writing anything
+- diff_model: Compare an existing model with a field spec
+- list_components: Describe the supported components and their parameters
+
+Naming helpers:
+- to_snake_case, pluralize, singularize: Name normalization used by the generators
 
 CLI utility functions:
 - migrate_db: Execute database migration
@@ -18,7 +28,17 @@ from ._loco_bindings import (
     generate_model,
     generate_scaffold,
     generate_controller_view,
+    generate_migration,
+    generate_alter_migration,
+    generate_seed,
+    render_model_preview,
     create_project,
+    validate_generation,
+    diff_model,
+    list_components,
+    to_snake_case,
+    pluralize,
+    singularize,
     migrate_db,
     rotate_keys,
     clean_temp,
@@ -31,7 +51,17 @@ __all__ = [
     "generate_model",
     "generate_scaffold",
     "generate_controller_view",
+    "generate_migration",
+    "generate_alter_migration",
+    "generate_seed",
+    "render_model_preview",
     "create_project",
+    "validate_generation",
+    "diff_model",
+    "list_components",
+    "to_snake_case",
+    "pluralize",
+    "singularize",
     "migrate_db",
     "rotate_keys",
     "clean_temp",
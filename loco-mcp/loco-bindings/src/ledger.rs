@@ -0,0 +1,101 @@
+//! Idempotency ledger for the privileged operations
+//!
+//! Results of completed calls made with an idempotency key are kept in
+//! `.loco/idempotency.json` under the project, so a retried call returns the
+//! recorded result instead of running the operation again. Callers hold the
+//! project's [`ProjectLock`](crate::lock::ProjectLock) from the lookup until
+//! the call is recorded, so concurrent calls with one key run it only once.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// Ledger file, relative to the project root
+pub const LEDGER_FILE: &str = ".loco/idempotency.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    operation: String,
+    key: String,
+    /// Unix time in seconds
+    completed_at: u64,
+    result: serde_json::Value,
+}
+
+pub struct Ledger {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl Ledger {
+    /// Open the project's ledger, empty if it does not exist yet
+    pub fn open(project_root: &Path) -> Result<Self, String> {
+        let path = project_root.join(LEDGER_FILE);
+        let entries = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| format!("Invalid idempotency ledger {}: {}", path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+        Ok(Self { path, entries })
+    }
+
+    /// Result recorded for `key` within the last `window`, if any
+    pub fn lookup(&self, operation: &str, key: &str, window: Duration) -> Option<&serde_json::Value> {
+        let now = now();
+        self.entries
+            .iter()
+            .find(|entry| {
+                entry.operation == operation
+                    && entry.key == key
+                    && now.saturating_sub(entry.completed_at) < window.as_secs()
+            })
+            .map(|entry| &entry.result)
+    }
+
+    /// Record a completed call, dropping this operation's expired entries
+    pub fn record(
+        &mut self,
+        operation: &str,
+        key: &str,
+        result: serde_json::Value,
+        window: Duration,
+    ) -> Result<(), String> {
+        let now = now();
+        self.entries.retain(|entry| {
+            entry.operation != operation
+                || (entry.key != key && now.saturating_sub(entry.completed_at) < window.as_secs())
+        });
+        self.entries.push(Entry {
+            operation: operation.to_string(),
+            key: key.to_string(),
+            completed_at: now,
+            result,
+        });
+        self.save()
+    }
+
+    fn save(&self) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let content = serde_json::to_string_pretty(&self.entries).map_err(|e| e.to_string())?;
+        // written aside and renamed over the ledger, so it is never left
+        // half written
+        let tmp = self.path.with_extension(format!("json.{}.tmp", std::process::id()));
+        std::fs::write(&tmp, content)
+            .map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+        std::fs::rename(&tmp, &self.path).map_err(|e| {
+            let _ = std::fs::remove_file(&tmp);
+            format!("Failed to write {}: {}", self.path.display(), e)
+        })
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
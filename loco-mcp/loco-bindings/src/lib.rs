@@ -4,14 +4,28 @@
 //! exposing model, scaffold, and controller generation to Python.
 
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
-use loco_gen::{self, Component, AppInfo, ScaffoldKind};
-use std::path::Path;
+use pyo3::types::{PyBool, PyDict, PyList, PyTuple};
+use loco_gen::{self, inflection::Inflections, template::{BuiltinTemplateSource, FileSystemTemplateSource, TemplateSource}, AppInfo, Component, GenerateOptions, RRgen, ScaffoldKind};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::{Duration, Instant};
 use std::fs;
-use regex;
 
 mod error;
+mod ledger;
+mod lock;
+mod naming;
+mod policy;
+mod schema;
+mod seed;
 use error::{ValidationError, FileOperationError, ProjectError};
+use ledger::Ledger;
+use lock::ProjectLock;
+use policy::OperationPolicy;
 
 /// Generate a Loco model
 ///
@@ -20,34 +34,28 @@ use error::{ValidationError, FileOperationError, ProjectError};
 ///     name (str): Name of the model (e.g., "user", "post")
 ///     fields (dict): Dictionary of field_name -> field_type mappings
 ///     with_timestamps (bool): Whether to include created_at/updated_at fields
+///     dry_run (bool): Render without writing to disk (default: False)
 ///
 /// Returns:
-///     dict: Generation result with created_files, messages, and success status
+///     dict: Generation result with created_files, messages, and success status.
+///           In dry-run mode, also previews (path -> rendered content)
 #[pyfunction]
+#[pyo3(signature = (project_path, name, fields, with_timestamps, dry_run = false))]
 fn generate_model(
     py: Python<'_>,
     project_path: &str,
     name: &str,
     fields: Bound<'_, PyDict>,
     with_timestamps: bool,
+    dry_run: bool,
 ) -> PyResult<PyObject> {
     // Parse fields from Python dict to Vec<(String, String)>
-    let field_list: Vec<(String, String)> = fields
-        .items()
-        .iter()
-        .map(|item| {
-            let key: String = item.get_item(0)?.extract()?;
-            let value: String = item.get_item(1)?.extract()?;
-            Ok((key, value))
-        })
-        .collect::<PyResult<Vec<_>>>()?;
+    let field_list = extract_fields(&fields)?;
+
+    validate_field_types(&field_list)?;
+    let inflections = load_inflections(Path::new(project_path))?;
+    let field_list = parse_field_constraints(&field_list, &inflections)?;
 
-    // Create the generator
-    let rrgen = loco_gen::new_generator();
-    
-    // Get app info from project
-    let app_info = get_app_info(project_path)?;
-    
     // Generate model component
     let component = Component::Model {
         name: name.to_string(),
@@ -55,15 +63,7 @@ fn generate_model(
         fields: field_list,
     };
     
-    let result = loco_gen::generate(&rrgen, component, &app_info)
-        .map_err(|e| PyErr::new::<ProjectError, _>(format!("Generation failed: {}", e)))?;
-    
-    // Convert result to Python dict
-    let response = PyDict::new_bound(py);
-    response.set_item("success", true)?;
-    response.set_item("messages", loco_gen::collect_messages(&result))?;
-    
-    Ok(response.into())
+    run_generation(py, project_path, component, dry_run)
 }
 
 /// Generate a Loco scaffold (model + controller + views)
@@ -74,10 +74,13 @@ fn generate_model(
 ///     fields (dict): Dictionary of field_name -> field_type mappings
 ///     kind (str): Scaffold kind - "api", "html", or "htmx"
 ///     with_timestamps (bool): Whether to include created_at/updated_at fields
+///     dry_run (bool): Render without writing to disk (default: False)
 ///
 /// Returns:
-///     dict: Generation result with created_files, messages, and success status
+///     dict: Generation result with created_files, messages, and success status.
+///           In dry-run mode, also previews (path -> rendered content)
 #[pyfunction]
+#[pyo3(signature = (project_path, name, fields, kind, with_timestamps, dry_run = false))]
 fn generate_scaffold(
     py: Python<'_>,
     project_path: &str,
@@ -85,51 +88,28 @@ fn generate_scaffold(
     fields: Bound<'_, PyDict>,
     kind: &str,
     with_timestamps: bool,
+    dry_run: bool,
 ) -> PyResult<PyObject> {
     // Parse fields
-    let field_list: Vec<(String, String)> = fields
-        .items()
-        .iter()
-        .map(|item| {
-            let key: String = item.get_item(0)?.extract()?;
-            let value: String = item.get_item(1)?.extract()?;
-            Ok((key, value))
-        })
-        .collect::<PyResult<Vec<_>>>()?;
+    let field_list = extract_fields(&fields)?;
+
+    validate_field_types(&field_list)?;
+    let inflections = load_inflections(Path::new(project_path))?;
+    let field_list = parse_field_constraints(&field_list, &inflections)?;
 
     // Parse scaffold kind
-    let scaffold_kind = match kind.to_lowercase().as_str() {
-        "api" => ScaffoldKind::Api,
-        "html" => ScaffoldKind::Html,
-        "htmx" => ScaffoldKind::Htmx,
-        _ => return Err(PyErr::new::<ValidationError, _>(
-            format!("Invalid scaffold kind: {}. Must be 'api', 'html', or 'htmx'", kind)
-        )),
-    };
+    let scaffold_kind = parse_scaffold_kind("scaffold", kind)?;
 
-    // Create the generator
-    let rrgen = loco_gen::new_generator();
-    
-    // Get app info
-    let app_info = get_app_info(project_path)?;
-    
     // Generate scaffold component
     let component = Component::Scaffold {
         name: name.to_string(),
         with_tz: with_timestamps,
         fields: field_list,
         kind: scaffold_kind,
+        with_tracing: false,
     };
     
-    let result = loco_gen::generate(&rrgen, component, &app_info)
-        .map_err(|e| PyErr::new::<ProjectError, _>(format!("Generation failed: {}", e)))?;
-    
-    // Convert result to Python dict
-    let response = PyDict::new_bound(py);
-    response.set_item("success", true)?;
-    response.set_item("messages", loco_gen::collect_messages(&result))?;
-    
-    Ok(response.into())
+    run_generation(py, project_path, component, dry_run)
 }
 
 /// Generate a Loco controller with views
@@ -139,33 +119,24 @@ fn generate_scaffold(
 ///     name (str): Name of the controller (e.g., "users", "posts")
 ///     actions (list): List of action names (e.g., ["index", "show", "create"])
 ///     kind (str): Controller kind - "api", "html", or "htmx"
+///     dry_run (bool): Render without writing to disk (default: False)
 ///
 /// Returns:
-///     dict: Generation result with created_files, messages, and success status
+///     dict: Generation result with created_files, messages, and success status.
+///           In dry-run mode, also previews (path -> rendered content)
 #[pyfunction]
+#[pyo3(signature = (project_path, name, actions, kind, dry_run = false))]
 fn generate_controller_view(
     py: Python<'_>,
     project_path: &str,
     name: &str,
     actions: Vec<String>,
     kind: &str,
+    dry_run: bool,
 ) -> PyResult<PyObject> {
     // Parse scaffold kind (used for controller too)
-    let scaffold_kind = match kind.to_lowercase().as_str() {
-        "api" => ScaffoldKind::Api,
-        "html" => ScaffoldKind::Html,
-        "htmx" => ScaffoldKind::Htmx,
-        _ => return Err(PyErr::new::<ValidationError, _>(
-            format!("Invalid controller kind: {}. Must be 'api', 'html', or 'htmx'", kind)
-        )),
-    };
+    let scaffold_kind = parse_scaffold_kind("controller", kind)?;
 
-    // Create the generator
-    let rrgen = loco_gen::new_generator();
-    
-    // Get app info
-    let app_info = get_app_info(project_path)?;
-    
     // Generate controller component
     let component = Component::Controller {
         name: name.to_string(),
@@ -173,17 +144,1308 @@ fn generate_controller_view(
         kind: scaffold_kind,
     };
     
-    let result = loco_gen::generate(&rrgen, component, &app_info)
-        .map_err(|e| PyErr::new::<ProjectError, _>(format!("Generation failed: {}", e)))?;
-    
-    // Convert result to Python dict
+    run_generation(py, project_path, component, dry_run)
+}
+
+/// Generate a standalone migration
+///
+/// Args:
+///     project_path (str): Path to the Loco project root
+///     name (str): Migration name describing the change, as for `cargo loco generate
+///         migration` (e.g., "add_age_to_users", "remove_legacy_from_users")
+///     operations (list): Operations on a single table, all of the same kind:
+///         "add_column:<table>:<column>:<type>" or "drop_column:<table>:<column>[:<type>]".
+///         Types are Loco field types, or i16, i32, i64, f32, f64, bool, String
+///     dry_run (bool): Render without writing to disk (default: False)
+///
+/// Returns:
+///     dict: Generation result with created_files, messages, and success status.
+///           In dry-run mode, also previews (path -> rendered content)
+#[pyfunction]
+#[pyo3(signature = (project_path, name, operations, dry_run = false))]
+fn generate_migration(
+    py: Python<'_>,
+    project_path: &str,
+    name: &str,
+    operations: Vec<String>,
+    dry_run: bool,
+) -> PyResult<PyObject> {
+    if !naming::is_snake_case(name) {
+        return Err(PyErr::new::<ValidationError, _>(
+            format!("Invalid migration name '{}' (e.g. '{}')", name, naming::to_snake_case(name))
+        ));
+    }
+    if operations.is_empty() {
+        return Err(PyErr::new::<ValidationError, _>("operations cannot be empty"));
+    }
+
+    let operations = operations
+        .iter()
+        .map(|operation| parse_migration_operation(operation))
+        .collect::<PyResult<Vec<_>>>()?;
+
+    // loco-gen migrations change one table in one direction
+    let first = &operations[0];
+    if let Some(other) = operations.iter().find(|op| op.verb != first.verb || op.table != first.table) {
+        return Err(PyErr::new::<ValidationError, _>(format!(
+            "Operations in one migration must share a verb and table: '{}' on '{}' does not match '{}' on '{}'",
+            other.verb, other.table, first.verb, first.table
+        )));
+    }
+
+    // loco-gen infers the kind of migration and its table from the name
+    let inflections = load_inflections(Path::new(project_path))?;
+    let (prefix, joiner) = match first.verb {
+        "add_column" => ("add", "to"),
+        _ => ("remove", "from"),
+    };
+    let names_table = name
+        .strip_prefix(&format!("{}_", prefix))
+        .and_then(|rest| rest.rsplit_once(&format!("_{}_", joiner)))
+        .is_some_and(|(_, table)| inflections.pluralize(table) == inflections.pluralize(&first.table));
+    if !names_table || first.table.contains('_') {
+        let columns: Vec<&str> = operations.iter().map(|op| op.column.as_str()).collect();
+        return Err(PyErr::new::<ValidationError, _>(format!(
+            "Migration name '{}' does not describe the operations, expected e.g. '{}_{}_{}_{}'{}",
+            name,
+            prefix,
+            columns.join("_and_"),
+            joiner,
+            first.table,
+            if first.table.contains('_') { " (table names containing '_' are not supported)" } else { "" }
+        )));
+    }
+
+    let fields: Vec<(String, String)> = operations
+        .into_iter()
+        .map(|op| (op.column, op.field_type))
+        .collect();
+    validate_field_types(&fields)?;
+
+    let component = Component::Migration {
+        name: name.to_string(),
+        with_tz: false,
+        fields,
+    };
+
+    run_generation(py, project_path, component, dry_run)
+}
+
+/// Generate a migration bringing a model's table in line with a diff
+///
+/// Added columns are added and removed ones dropped, and a column whose type
+/// changed is altered in place, which SQLite does not support. A reference
+/// whose type changed cannot be altered, so it is dropped and added again,
+/// which loses the data in it; a warning is included in the messages. `down`
+/// reverses every step.
+///
+/// Args:
+///     project_path (str): Path to the Loco project root
+///     model_name (str): Name of the model (e.g., "user", "post")
+///     diff (dict): Columns to change, as returned by diff_model: added and
+///         removed (lists of dicts with name and type) and changed (list of
+///         dicts with name, from and to)
+///     dry_run (bool): Render without writing to disk (default: False)
+///
+/// Returns:
+///     dict: Generation result with created_files, messages, and success status.
+///           In dry-run mode, also previews (path -> rendered content)
+#[pyfunction]
+#[pyo3(signature = (project_path, model_name, diff, dry_run = false))]
+fn generate_alter_migration(
+    py: Python<'_>,
+    project_path: &str,
+    model_name: &str,
+    diff: Bound<'_, PyDict>,
+    dry_run: bool,
+) -> PyResult<PyObject> {
+    get_app_info(project_path)?;
+    let project_root = Path::new(project_path);
+    let table = load_inflections(project_root)?.pluralize(&naming::to_snake_case(model_name));
+    let current = schema::load(project_root, &table)
+        .map_err(PyErr::new::<FileOperationError, _>)?
+        .ok_or_else(|| {
+            PyErr::new::<ProjectError, _>(format!(
+                "Model '{}' not found: no migration creates table '{}'",
+                model_name, table
+            ))
+        })?;
+
+    let invalid = |message: String| PyErr::new::<ValidationError, _>(format!("Invalid diff: {}", message));
+    let columns = |key: &str, fields: &[&str]| -> PyResult<Vec<Vec<String>>> {
+        let Some(items) = diff.get_item(key)? else {
+            return Ok(Vec::new());
+        };
+        let mut columns = Vec::new();
+        for item in items.iter()? {
+            let item = item?;
+            let values = fields
+                .iter()
+                .map(|field| {
+                    item.get_item(field)
+                        .and_then(|value| value.extract::<String>())
+                        .map_err(|_| invalid(format!("each {} column needs a '{}' string", key, field)))
+                })
+                .collect::<PyResult<Vec<_>>>()?;
+            for field_type in &values[1..] {
+                schema::validate_column_type(field_type)
+                    .map_err(|e| invalid(format!("column '{}': {}", values[0], e)))?;
+            }
+            columns.push(values);
+        }
+        Ok(columns)
+    };
+
+    // the diff must still describe the table, or `down` would not restore it
+    let mut changes = Vec::new();
+    let mut warnings = Vec::new();
+    for column in columns("changed", &["name", "from", "to"])? {
+        let [name, from, to] = <[String; 3]>::try_from(column).unwrap_or_default();
+        if current.columns.get(&name) != Some(&from) {
+            return Err(invalid(format!("column '{}' is not of type '{}' in '{}'", name, from, table)));
+        }
+        if !schema::is_reference(&from) && !schema::is_reference(&to) {
+            changes.push(schema::Change::Alter(name, from, to));
+            continue;
+        }
+        warnings.push(format!(
+            "Changing the type of `{}` from {} to {} drops and re-adds the column, its data is lost.",
+            name, from, to
+        ));
+        changes.push(schema::Change::Remove(name.clone(), from));
+        changes.push(schema::Change::Add(name, to));
+    }
+    for column in columns("removed", &["name", "type"])? {
+        let [name, field_type] = <[String; 2]>::try_from(column).unwrap_or_default();
+        if current.columns.get(&name) != Some(&field_type) {
+            return Err(invalid(format!("column '{}' is not of type '{}' in '{}'", name, field_type, table)));
+        }
+        changes.push(schema::Change::Remove(name, field_type));
+    }
+    for column in columns("added", &["name", "type"])? {
+        let [name, field_type] = <[String; 2]>::try_from(column).unwrap_or_default();
+        if current.columns.contains_key(&name) {
+            return Err(invalid(format!("column '{}' already exists in '{}'", name, table)));
+        }
+        changes.push(schema::Change::Add(name, field_type));
+    }
+    if changes.is_empty() {
+        return Err(invalid("no columns to change".to_string()));
+    }
+
+    let up: Vec<String> = changes.iter().map(|change| change.statement(&table)).collect();
+    let down: Vec<String> = changes
+        .iter()
+        .rev()
+        .map(|change| change.inverse().statement(&table))
+        .collect();
+    let statements = |statements: &[String]| {
+        statements
+            .iter()
+            .map(|statement| format!("        {}\n", statement))
+            .collect::<String>()
+    };
+    let body = ALTER_MIGRATION_BODY
+        .replace("{up}", &statements(&up))
+        .replace("{down}", &statements(&down));
+
+    // loco-gen renders migrations it cannot infer from the name with
+    // `migration/empty.t`, so the statements take the place of that template
+    let templates = OverlayTemplateSource {
+        project: project_templates(project_root),
+        path: Path::new("migration/empty.t"),
+        content: format!("{}{{% raw %}}{}{{% endraw %}}", ALTER_MIGRATION_FRONT_MATTER, body),
+    };
+    let component = Component::Migration {
+        name: format!("alter_{}", table),
+        with_tz: false,
+        fields: Vec::new(),
+    };
+    let response = run_generation_with(py, project_path, component, Box::new(templates), dry_run)?;
+
+    let response = response.downcast_bound::<PyDict>(py)?;
+    let created: Vec<String> = response
+        .get_item("created_files")?
+        .map(|files| files.extract())
+        .transpose()?
+        .unwrap_or_default();
+    if created.is_empty() {
+        return Err(PyErr::new::<ProjectError, _>(format!(
+            "A migration altering '{}' was already generated this second, try again",
+            table
+        )));
+    }
+    let messages: String = response
+        .get_item("messages")?
+        .map(|messages| messages.extract())
+        .transpose()?
+        .unwrap_or_default();
+    // the only template rendered is the overlay, which loco-gen goes on to
+    // list as sourced from the local templates after a blank line
+    let mut messages = match messages.split_once("\n\n") {
+        Some((generated, _)) => format!("{}\n", generated),
+        None => messages,
+    };
+    for warning in &warnings {
+        messages.push_str(&format!("* Warning: {}\n", warning));
+    }
+    response.set_item("messages", messages)?;
+
+    Ok(response.clone().unbind().into())
+}
+
+/// Front matter of the migration written by generate_alter_migration, in the
+/// form of loco-gen's migration templates
+const ALTER_MIGRATION_FRONT_MATTER: &str = r#"{% set mig_ts = ts | date(format="%Y%m%d_%H%M%S") -%}
+{% set module_name = "m" ~  mig_ts ~ "_" ~ name -%}
+to: "migration/src/{{module_name}}.rs"
+skip_exists: true
+message: "Migration `{{name}}` added! You can now apply it with `$ cargo loco db migrate && cargo loco db entities`."
+injections:
+- into: "migration/src/lib.rs"
+  before: "inject-above"
+  content: "            Box::new({{module_name}}::Migration),"
+- into: "migration/src/lib.rs"
+  before: "pub struct Migrator"
+  content: "mod {{module_name}};"
+---
+"#;
+
+/// Body of the migration written by generate_alter_migration, with `{up}`
+/// and `{down}` replaced by their statements
+const ALTER_MIGRATION_BODY: &str = r#"use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+{up}        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+{down}        Ok(())
+    }
+}
+"#;
+
+/// Validate a model or scaffold spec without generating anything
+///
+/// Runs the same name, field and project checks as generation and reports
+/// models that already have a migration, without writing to disk.
+///
+/// Args:
+///     project_path (str): Path to the Loco project root
+///     name (str): Name of the model (e.g., "user", "post")
+///     fields (dict): Dictionary of field_name -> field_type mappings
+///
+/// Returns:
+///     dict: Validation result with success status, errors (list of dicts with
+///           error_type and message) and an always empty created_files
+#[pyfunction]
+fn validate_generation(
+    py: Python<'_>,
+    project_path: &str,
+    name: &str,
+    fields: Bound<'_, PyDict>,
+) -> PyResult<PyObject> {
+    let field_list = extract_fields(&fields)?;
+    let mut errors = Vec::new();
+    let inflections = load_inflections(Path::new(project_path)).unwrap_or_else(|err| {
+        errors.push(err);
+        naming::builtin_inflections()
+    });
+
+    let snake_name = naming::to_snake_case(name);
+    if !naming::is_snake_case(&snake_name) {
+        errors.push(PyErr::new::<ValidationError, _>(format!(
+            "Invalid name '{}'. Must start with a letter and contain only letters, numbers, and underscores",
+            name
+        )));
+    }
+
+    for field in &field_list {
+        let field = std::slice::from_ref(field);
+        if let Err(err) = validate_field_types(field).and_then(|()| parse_field_constraints(field, &inflections)) {
+            errors.push(err);
+        }
+    }
+
+    match get_app_info(project_path) {
+        Ok(_) => {
+            let table = inflections.pluralize(&snake_name);
+            let existing = fs::read_dir(Path::new(project_path).join("migration").join("src"))
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| entry.path())
+                .find(|path| {
+                    path.file_name()
+                        .and_then(|file_name| file_name.to_str())
+                        .is_some_and(|file_name| {
+                            file_name.starts_with('m') && file_name.ends_with(&format!("_{}.rs", table))
+                        })
+                });
+            if let Some(path) = existing {
+                errors.push(PyErr::new::<ProjectError, _>(format!(
+                    "Model '{}' already exists: {}",
+                    snake_name,
+                    path.display()
+                )));
+            }
+        }
+        Err(err) => errors.push(err),
+    }
+
+    let error_list = PyList::empty_bound(py);
+    for err in &errors {
+        let error = PyDict::new_bound(py);
+        error.set_item("error_type", err.get_type_bound(py).name()?)?;
+        error.set_item("message", err.value_bound(py).to_string())?;
+        error_list.append(error)?;
+    }
+
+    let response = PyDict::new_bound(py);
+    response.set_item("success", errors.is_empty())?;
+    response.set_item("errors", error_list)?;
+    response.set_item("created_files", Vec::<String>::new())?;
+
+    Ok(response.into())
+}
+
+/// Compare an existing model with a field spec
+///
+/// The model's current columns are read from the project's migrations, so
+/// the diff can feed a migration that brings the table in line with the spec.
+///
+/// Args:
+///     project_path (str): Path to the Loco project root
+///     model_name (str): Name of the model (e.g., "user", "post")
+///     fields (dict): Dictionary of field_name -> field_type mappings
+///
+/// Returns:
+///     dict: Diff with the table, the migrations that define it, and added,
+///           removed (lists of dicts with name and type) and changed (list of
+///           dicts with name, from and to) columns
+#[pyfunction]
+fn diff_model(
+    py: Python<'_>,
+    project_path: &str,
+    model_name: &str,
+    fields: Bound<'_, PyDict>,
+) -> PyResult<PyObject> {
+    get_app_info(project_path)?;
+    let inflections = load_inflections(Path::new(project_path))?;
+    let field_list = extract_fields(&fields)?;
+    validate_field_types(&field_list)?;
+    let field_list = parse_field_constraints(&field_list, &inflections)?;
+    let requested = schema::columns_from_fields(&field_list)
+        .map_err(|e| PyErr::new::<ValidationError, _>(format!("Invalid fields: {}", e)))?;
+
+    let table = inflections.pluralize(&naming::to_snake_case(model_name));
+    let current = schema::load(Path::new(project_path), &table)
+        .map_err(PyErr::new::<FileOperationError, _>)?
+        .ok_or_else(|| {
+            PyErr::new::<ProjectError, _>(format!(
+                "Model '{}' not found: no migration creates table '{}'",
+                model_name, table
+            ))
+        })?;
+    let diff = schema::diff(&current.columns, &requested);
+
+    let columns = |columns: &[(String, String)]| -> PyResult<Vec<Bound<'_, PyDict>>> {
+        columns
+            .iter()
+            .map(|(name, field_type)| {
+                let column = PyDict::new_bound(py);
+                column.set_item("name", name)?;
+                column.set_item("type", field_type)?;
+                Ok(column)
+            })
+            .collect()
+    };
+    let changed = diff
+        .changed
+        .iter()
+        .map(|(name, from, to)| {
+            let column = PyDict::new_bound(py);
+            column.set_item("name", name)?;
+            column.set_item("from", from)?;
+            column.set_item("to", to)?;
+            Ok(column)
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+
     let response = PyDict::new_bound(py);
     response.set_item("success", true)?;
-    response.set_item("messages", loco_gen::collect_messages(&result))?;
-    
+    response.set_item("table", &table)?;
+    response.set_item("migrations", &current.migrations)?;
+    response.set_item("has_changes", !diff.is_empty())?;
+    response.set_item("added", columns(&diff.added)?)?;
+    response.set_item("removed", columns(&diff.removed)?)?;
+    response.set_item("changed", changed)?;
+
+    Ok(response.into())
+}
+
+/// Generate a seed data file for a model
+///
+/// Writes the records to `src/fixtures/<table>.yaml` and adds loading it to
+/// the app's `Hooks::seed`, so that `cargo loco db seed` inserts them. When
+/// the model's entity exists, record keys are checked against its columns.
+///
+/// Args:
+///     project_path (str): Path to the Loco project root
+///     name (str): Name of the model (e.g., "user", "post")
+///     records (list): Rows to insert, as dicts of column name to value
+///
+/// Returns:
+///     dict: Generation result with created_files, messages, registered (whether
+///           the seed hook loads the file) and success status
+#[pyfunction]
+fn generate_seed(
+    py: Python<'_>,
+    project_path: &str,
+    name: &str,
+    records: Vec<Bound<'_, PyDict>>,
+) -> PyResult<PyObject> {
+    get_app_info(project_path)?;
+    if records.is_empty() {
+        return Err(PyErr::new::<ValidationError, _>("records cannot be empty"));
+    }
+    let project_root = Path::new(project_path);
+    let table = load_inflections(project_root)?.pluralize(&naming::to_snake_case(name));
+
+    let columns = seed::entity_columns(project_root, &table).map_err(PyErr::new::<FileOperationError, _>)?;
+    let rows = records
+        .iter()
+        .map(|record| py_to_json(record.as_any()))
+        .collect::<PyResult<Vec<_>>>()?;
+    if let Some(columns) = &columns {
+        for (index, row) in rows.iter().enumerate() {
+            let unknown: Vec<&String> = row
+                .as_object()
+                .into_iter()
+                .flat_map(|row| row.keys())
+                .filter(|key| !columns.contains(key))
+                .collect();
+            if !unknown.is_empty() {
+                return Err(PyErr::new::<ValidationError, _>(format!(
+                    "Record {} has unknown columns {:?} for model '{}'. Columns: {}",
+                    index,
+                    unknown,
+                    name,
+                    columns.join(", ")
+                )));
+            }
+        }
+    }
+
+    let _lock = lock_project(py, project_root)?;
+    let relative = format!("{}/{}.yaml", seed::FIXTURES_DIR, table);
+    let path = project_root.join(&relative);
+    if path.exists() {
+        return Err(PyErr::new::<FileOperationError, _>(
+            format!("Seed file already exists: {}", relative)
+        ));
+    }
+    let content = serde_yaml::to_string(&rows)
+        .map_err(|e| PyErr::new::<ProjectError, _>(format!("Failed to serialize records: {}", e)))?;
+
+    // the seed file and its registration are added together or not at all
+    let (existing_files, existing_dirs) = list_project_entries(project_root);
+    let originals = read_existing_files(project_root, &["src/app.rs"]);
+    let registered = fs::create_dir_all(project_root.join(seed::FIXTURES_DIR))
+        .and_then(|()| fs::write(&path, content))
+        .map_err(|e| format!("Failed to write {}: {}", relative, e))
+        .and_then(|()| seed::register(project_root, &table))
+        .map_err(|e| {
+            roll_back_generation(project_root, &existing_files, &existing_dirs, &originals);
+            PyErr::new::<FileOperationError, _>(e)
+        })?;
+    let mut messages = format!("* Seed `{}` with {} records added.\n", relative, rows.len());
+    if !registered {
+        messages.push_str(&format!(
+            "* Warning: No `Hooks::seed` found in src/app.rs, load the file there with `db::seed::<{}::ActiveModel>`.\n",
+            table
+        ));
+    }
+
+    let response = PyDict::new_bound(py);
+    response.set_item("success", true)?;
+    response.set_item("created_files", created_files(project_root, &existing_files))?;
+    response.set_item("messages", messages)?;
+    response.set_item("registered", registered)?;
+
+    Ok(response.into())
+}
+
+/// Render a model without a project
+///
+/// Nothing is read from or written to disk, so no Loco project (or
+/// Cargo.toml) is needed. The built-in model templates are rendered with the
+/// same engine generation uses; `model` is `model/model.t`, the migration
+/// creating the model's table.
+///
+/// Args:
+///     name (str): Name of the model (e.g., "user", "post")
+///     fields (dict): Dictionary of field_name -> field_type mappings
+///     with_timestamps (bool): Whether to include created_at/updated_at fields (default: True)
+///     app_name (str): Crate name the templates render with (default: "preview")
+///
+/// Returns:
+///     dict: success status, the rendered model, and files (relative path ->
+///           rendered content of each file the model generator would create)
+#[pyfunction]
+#[pyo3(signature = (name, fields, with_timestamps = true, app_name = "preview"))]
+fn render_model_preview(
+    py: Python<'_>,
+    name: &str,
+    fields: Bound<'_, PyDict>,
+    with_timestamps: bool,
+    app_name: &str,
+) -> PyResult<PyObject> {
+    let field_list = extract_fields(&fields)?;
+    validate_field_types(&field_list)?;
+    let inflections = naming::builtin_inflections();
+    let field_list = parse_field_constraints(&field_list, &inflections)?;
+
+    let component = Component::Model {
+        name: name.to_string(),
+        with_tz: with_timestamps,
+        fields: field_list,
+    };
+    let app_info = AppInfo { app_name: app_name.to_string() };
+    let options = GenerateOptions {
+        templates: Box::new(BuiltinTemplateSource),
+        inflections,
+        run_migration: false,
+    };
+    let rendered = loco_gen::preview(component, &app_info, &options)
+        .map_err(|e| PyErr::new::<ProjectError, _>(format!("Rendering failed: {}", e)))?;
+    let model = rendered
+        .iter()
+        .find(|file| file.template == Path::new("model/model.t"))
+        .map(|file| file.content.clone())
+        .ok_or_else(|| PyErr::new::<ProjectError, _>("Rendering failed: model/model.t not rendered"))?;
+
+    let files = PyDict::new_bound(py);
+    for file in &rendered {
+        files.set_item(&file.path, &file.content)?;
+    }
+
+    let response = PyDict::new_bound(py);
+    response.set_item("success", true)?;
+    response.set_item("model", model)?;
+    response.set_item("files", files)?;
+
     Ok(response.into())
 }
 
+/// Describe the components the generators support
+///
+/// Built from the same constants the generators validate against, so a
+/// frontend can build forms from it without drifting.
+///
+/// Returns:
+///     dict: Component name ("model", "scaffold", "controller", "migration") to
+///           its parameters and, where relevant, accepted field types, field
+///           constraint grammar, kinds and operations
+#[pyfunction]
+fn list_components(py: Python<'_>) -> PyResult<PyObject> {
+    use serde_json::json;
+
+    let param = |name: &str, kind: &str, required: bool| json!({"name": name, "type": kind, "required": required});
+    let dry_run = json!({"name": "dry_run", "type": "bool", "required": false, "default": false});
+    let fields = json!({
+        "types": accepted_field_types(),
+        "constraints": FIELD_CONSTRAINTS,
+        "foreign_key": {"syntax": "foreign_key:<table>", "types": FOREIGN_KEY_TYPES},
+        "default": {"syntax": "default:<value>"},
+        "grammar": "<type>[:<param>...][:<constraint>...][:foreign_key:<table>][:default:<value>]",
+    });
+    let rust_types: serde_json::Map<String, serde_json::Value> = RUST_FIELD_TYPES
+        .iter()
+        .map(|(rust_type, loco_type)| (rust_type.to_string(), json!(loco_type)))
+        .collect();
+
+    let components = json!({
+        "model": {
+            "parameters": [
+                param("project_path", "str", true),
+                param("name", "str", true),
+                param("fields", "dict", true),
+                param("with_timestamps", "bool", true),
+                dry_run,
+            ],
+            "fields": fields,
+        },
+        "scaffold": {
+            "parameters": [
+                param("project_path", "str", true),
+                param("name", "str", true),
+                param("fields", "dict", true),
+                param("kind", "str", true),
+                param("with_timestamps", "bool", true),
+                dry_run,
+            ],
+            "fields": fields,
+            "kinds": ScaffoldKind::names(),
+        },
+        "controller": {
+            "parameters": [
+                param("project_path", "str", true),
+                param("name", "str", true),
+                param("actions", "list", true),
+                param("kind", "str", true),
+                dry_run,
+            ],
+            "kinds": ScaffoldKind::names(),
+        },
+        "migration": {
+            "parameters": [
+                param("project_path", "str", true),
+                param("name", "str", true),
+                param("operations", "list", true),
+                dry_run,
+            ],
+            "operations": MIGRATION_VERBS,
+            "grammar": "<operation>:<table>:<column>[:<type>]",
+            "types": accepted_field_types(),
+            "rust_types": rust_types,
+        },
+    });
+
+    json_to_py(py, &components)
+}
+
+/// Convert a name to snake_case (e.g., "BlogPost" -> "blog_post")
+#[pyfunction]
+#[pyo3(name = "to_snake_case")]
+fn to_snake_case_name(name: &str) -> String {
+    naming::to_snake_case(name)
+}
+
+/// Plural form of a name (e.g., "category" -> "categories")
+#[pyfunction]
+#[pyo3(name = "pluralize")]
+fn pluralize_name(word: &str) -> String {
+    naming::pluralize(word)
+}
+
+/// Singular form of a name (e.g., "people" -> "person")
+#[pyfunction]
+#[pyo3(name = "singularize")]
+fn singularize_name(word: &str) -> String {
+    naming::singularize(word)
+}
+
+/// Extract field_name -> field_type pairs from a Python dict
+fn extract_fields(fields: &Bound<'_, PyDict>) -> PyResult<Vec<(String, String)>> {
+    fields
+        .items()
+        .iter()
+        .map(|item| {
+            let key: String = item.get_item(0)?.extract()?;
+            let value: String = item.get_item(1)?.extract()?;
+            Ok((key, value))
+        })
+        .collect()
+}
+
+/// Parse the kind of a scaffold or controller, ignoring case
+fn parse_scaffold_kind(component: &str, kind: &str) -> PyResult<ScaffoldKind> {
+    kind.parse().map_err(|_| {
+        PyErr::new::<ValidationError, _>(format!(
+            "Invalid {} kind: {}. Must be one of: {}",
+            component,
+            kind,
+            ScaffoldKind::names().join(", ")
+        ))
+    })
+}
+
+/// Verbs accepted in migration operations
+const MIGRATION_VERBS: &[&str] = &["add_column", "drop_column"];
+
+/// Rust type names accepted in migration operations, with their Loco field type
+const RUST_FIELD_TYPES: &[(&str, &str)] = &[
+    ("i16", "small_int"),
+    ("i32", "int"),
+    ("i64", "big_int"),
+    ("f32", "float"),
+    ("f64", "double"),
+    ("bool", "bool"),
+    ("String", "string"),
+];
+
+/// Field type a dropped column is restored with when the operation gives none
+const DROPPED_COLUMN_TYPE: &str = "text";
+
+/// A single `generate_migration` operation
+struct MigrationOperation {
+    verb: &'static str,
+    table: String,
+    column: String,
+    field_type: String,
+}
+
+/// Parse `add_column:<table>:<column>:<type>` or `drop_column:<table>:<column>[:<type>]`
+fn parse_migration_operation(operation: &str) -> PyResult<MigrationOperation> {
+    let invalid = |reason: String| PyErr::new::<ValidationError, _>(
+        format!("Invalid migration operation '{}': {}", operation, reason)
+    );
+
+    let mut parts = operation.splitn(4, ':');
+    let verb = parts.next().unwrap_or_default();
+    let verb = MIGRATION_VERBS.iter().find(|known| **known == verb).ok_or_else(|| {
+        invalid(format!(
+            "unknown operation '{}'. Must be one of: {}",
+            verb,
+            MIGRATION_VERBS.join(", ")
+        ))
+    })?;
+    let table = parts.next().unwrap_or_default();
+    let column = parts.next().unwrap_or_default();
+    for (label, identifier) in [("table", table), ("column", column)] {
+        if !naming::is_snake_case(identifier) {
+            return Err(invalid(format!("'{}' is not a valid {} name", identifier, label)));
+        }
+    }
+
+    let field_type = match (*verb, parts.next()) {
+        (_, Some(field_type)) => RUST_FIELD_TYPES
+            .iter()
+            .find(|(rust_type, _)| *rust_type == field_type)
+            .map_or(field_type, |(_, loco_type)| loco_type),
+        ("drop_column", None) => DROPPED_COLUMN_TYPE,
+        (_, None) => return Err(invalid("add_column requires a type".to_string())),
+    };
+
+    Ok(MigrationOperation {
+        verb,
+        table: table.to_string(),
+        column: column.to_string(),
+        field_type: field_type.to_string(),
+    })
+}
+
+/// Field types accepted on top of the loco-gen type mappings
+const REFERENCE_FIELD_TYPES: &[&str] = &["references", "references?"];
+
+/// Constraints accepted after a field type, as in `string:unique`
+const FIELD_CONSTRAINTS: &[&str] = &["unique", "required", "not_null", "nullable"];
+
+/// Base types that can be declared as `foreign_key:<table>`
+const FOREIGN_KEY_TYPES: &[&str] = &["references", "int", "big_int"];
+
+/// Every field type accepted by the generators
+fn accepted_field_types() -> Vec<&'static str> {
+    let mut accepted: Vec<&str> = REFERENCE_FIELD_TYPES.to_vec();
+    accepted.extend(loco_gen::get_mappings().all_names().into_iter().map(String::as_str));
+    accepted
+}
+
+/// Validate that every field type is supported by loco-gen
+///
+/// Only the base type is checked, so parameterized types such as
+/// `decimal_len:10:2` or `references:author_id` are accepted.
+fn validate_field_types(fields: &[(String, String)]) -> PyResult<()> {
+    let accepted = accepted_field_types();
+
+    for (name, field_type) in fields {
+        let base_type = field_type.split(':').next().unwrap_or_default();
+        if accepted.contains(&base_type) {
+            continue;
+        }
+
+        let suggestion = accepted
+            .iter()
+            .min_by_key(|candidate| edit_distance(base_type, candidate))
+            .map(|candidate| format!(" Did you mean '{}'?", candidate))
+            .unwrap_or_default();
+        return Err(PyErr::new::<ValidationError, _>(format!(
+            "Invalid type '{}' for field '{}'.{} Accepted types: {}",
+            field_type,
+            name,
+            suggestion,
+            accepted.join(", ")
+        )));
+    }
+
+    Ok(())
+}
+
+/// Translate `type:constraint...` field definitions into loco-gen field types
+///
+/// Supported constraints are `unique` (`string^`), `required`/`not_null`
+/// (`string!`), `nullable` (`string`) and `foreign_key:<table>`, which turns
+/// the field into a reference to `<table>` stored in a column of the field's
+/// name. Tokens that are not constraints are kept as type parameters, e.g.
+/// `decimal_len:10:2:unique`. A trailing `default:<value>` sets the column's
+/// default and makes it required, as only non-null columns take one.
+fn parse_field_constraints(
+    fields: &[(String, String)],
+    inflections: &Inflections,
+) -> PyResult<Vec<(String, String)>> {
+    let mappings = loco_gen::get_mappings();
+    let names = mappings.all_names();
+    let invalid = |name: &str, message: String| {
+        PyErr::new::<ValidationError, _>(format!("Invalid field '{}': {}", name, message))
+    };
+
+    let mut parsed = Vec::with_capacity(fields.len());
+    for (name, definition) in fields {
+        let mut tokens = definition.split(':');
+        let base_type = tokens.next().unwrap_or_default();
+        let mut params = Vec::new();
+        let mut constraints = Vec::new();
+        let mut foreign_table = None;
+        let mut default = None;
+        while let Some(token) = tokens.next() {
+            match token {
+                // `primary_key` is recognized only to reject it with a clear message below
+                constraint if FIELD_CONSTRAINTS.contains(&constraint) || constraint == "primary_key" => {
+                    constraints.push(constraint);
+                }
+                "foreign_key" => {
+                    let table = tokens.next().filter(|table| !table.is_empty()).ok_or_else(|| {
+                        invalid(name, "`foreign_key` requires a table, e.g. `foreign_key:users`".to_string())
+                    })?;
+                    foreign_table = Some(table);
+                }
+                "default" => {
+                    let value = tokens.by_ref().collect::<Vec<_>>().join(":");
+                    if value.is_empty() {
+                        return Err(invalid(name, "`default` requires a value, e.g. `default:active`".to_string()));
+                    }
+                    default = Some(value);
+                }
+                param if constraints.is_empty() && foreign_table.is_none() => params.push(param),
+                other => {
+                    return Err(invalid(name, format!("unknown constraint '{}'", other)));
+                }
+            }
+        }
+
+        // loco-gen types with a nullability suffix take a default as they are
+        let sets_nullability = base_type.ends_with(['!', '^', '?']);
+        if constraints.is_empty() && foreign_table.is_none() && (default.is_none() || sets_nullability) {
+            if default.is_some() {
+                validate_default(name, definition)?;
+            }
+            parsed.push((name.clone(), definition.clone()));
+            continue;
+        }
+
+        let has = |constraint: &str| constraints.contains(&constraint);
+        if has("nullable") && default.is_some() {
+            return Err(invalid(name, "'nullable' columns cannot have a default".to_string()));
+        }
+        for conflicting in ["unique", "required", "not_null", "primary_key"] {
+            if has("nullable") && has(conflicting) {
+                return Err(invalid(
+                    name,
+                    format!("constraints 'nullable' and '{}' conflict", conflicting),
+                ));
+            }
+        }
+        if has("primary_key") {
+            return Err(invalid(
+                name,
+                "'primary_key' is not supported, an `id` primary key is always generated".to_string(),
+            ));
+        }
+        if sets_nullability {
+            return Err(invalid(
+                name,
+                format!("type '{}' already sets nullability, remove the suffix to use constraints", base_type),
+            ));
+        }
+
+        if let Some(table) = foreign_table {
+            if default.is_some() {
+                return Err(invalid(name, "foreign keys cannot have a default".to_string()));
+            }
+            if has("unique") {
+                return Err(invalid(name, "foreign keys cannot be unique".to_string()));
+            }
+            if !params.is_empty() || !FOREIGN_KEY_TYPES.contains(&base_type) {
+                return Err(invalid(
+                    name,
+                    format!("type '{}' cannot be used as a foreign key", definition),
+                ));
+            }
+            let reference = if has("nullable") { "references?" } else { "references" };
+            parsed.push((
+                inflections.singularize(table),
+                format!("{}:{}", reference, name),
+            ));
+            continue;
+        }
+
+        let suffix = if has("unique") {
+            "^"
+        } else if has("required") || has("not_null") || default.is_some() {
+            "!"
+        } else {
+            ""
+        };
+        let field_type = format!("{}{}", base_type, suffix);
+        if !names.iter().any(|known| **known == field_type) {
+            return Err(invalid(
+                name,
+                format!("type '{}' does not support the given constraints", base_type),
+            ));
+        }
+        let mut field_type = std::iter::once(field_type.as_str())
+            .chain(params)
+            .collect::<Vec<_>>()
+            .join(":");
+        if let Some(value) = default {
+            field_type = format!("{}:default:{}", field_type, value);
+            validate_default(name, &field_type)?;
+        }
+        parsed.push((name.clone(), field_type));
+    }
+
+    Ok(parsed)
+}
+
+/// Check that a field's default value fits its column type
+fn validate_default(name: &str, field_type: &str) -> PyResult<()> {
+    loco_gen::get_columns_and_references(&[(name.to_string(), field_type.to_string())])
+        .map(|_| ())
+        .map_err(|e| PyErr::new::<ValidationError, _>(format!("Invalid field '{}': {}", name, e)))
+}
+
+/// Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                prev
+            } else {
+                1 + prev.min(row[j]).min(current)
+            };
+            prev = current;
+        }
+    }
+    row[b.len()]
+}
+
+/// Create a generator writing into the project directory
+fn new_generator(project_path: &str) -> RRgen {
+    RRgen::with_working_dir(project_path).add_template_engine(loco_gen::tera_ext::new())
+}
+
+/// Directories never touched by generation
+const IGNORED_DIRS: &[&str] = &["target", ".git", "node_modules"];
+
+/// List all files under the project root
+fn list_project_files(root: &Path) -> HashSet<PathBuf> {
+    list_project_entries(root).0
+}
+
+/// List all files and directories under the project root
+fn list_project_entries(root: &Path) -> (HashSet<PathBuf>, HashSet<PathBuf>) {
+    let mut files = HashSet::new();
+    let mut visited = HashSet::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        visited.insert(dir);
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                let ignored = path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| IGNORED_DIRS.contains(&name));
+                if !ignored {
+                    dirs.push(path);
+                }
+            } else {
+                files.insert(path);
+            }
+        }
+    }
+    (files, visited)
+}
+
+/// Contents of the given project files, relative to the project root, which
+/// exist
+fn read_existing_files(root: &Path, files: &[&str]) -> HashMap<PathBuf, Vec<u8>> {
+    files
+        .iter()
+        .map(|relative| root.join(relative))
+        .filter_map(|path| fs::read(&path).ok().map(|content| (path, content)))
+        .collect()
+}
+
+/// Undo a failed generation: remove what it created and write back the
+/// original content of the existing files it touched
+///
+/// Returns the removed files.
+fn roll_back_generation(
+    root: &Path,
+    existing_files: &HashSet<PathBuf>,
+    existing_dirs: &HashSet<PathBuf>,
+    originals: &HashMap<PathBuf, Vec<u8>>,
+) -> Vec<String> {
+    for (path, content) in originals {
+        let _ = fs::write(path, content);
+    }
+
+    let (files, dirs) = list_project_entries(root);
+
+    let mut removed = Vec::new();
+    for file in files.difference(existing_files) {
+        if fs::remove_file(file).is_ok() {
+            removed.push(file.to_string_lossy().to_string());
+        }
+    }
+    removed.sort();
+
+    // deepest first, so nested directories are emptied before their parents
+    let mut created_dirs: Vec<&PathBuf> = dirs.difference(existing_dirs).collect();
+    created_dirs.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+    for dir in created_dirs {
+        let _ = fs::remove_dir(dir);
+    }
+
+    removed
+}
+
+/// Files present under the project root that were not there before
+fn created_files(root: &Path, existing: &HashSet<PathBuf>) -> Vec<String> {
+    let mut created: Vec<String> = list_project_files(root)
+        .difference(existing)
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+    created.sort();
+    created
+}
+
+/// Run a generator against the project and build the Python response
+///
+/// The project is locked while generating, dry runs included, so concurrent
+/// generations do not interleave their edits to shared files and dry runs
+/// see a consistent project. If generation fails part way, the files and
+/// directories it created are removed again and the files it injected into
+/// are restored. In dry-run mode the
+/// files the generator would touch are copied into a scratch directory and
+/// the generator runs there without migrating, so rendering and injection
+/// errors are still reported while the project itself is left untouched.
+fn run_generation(
+    py: Python<'_>,
+    project_path: &str,
+    component: Component,
+    dry_run: bool,
+) -> PyResult<PyObject> {
+    let templates = project_templates(Path::new(project_path));
+    run_generation_with(py, project_path, component, Box::new(templates), dry_run)
+}
+
+/// Run a generator as [`run_generation`] does, rendering from `templates`
+fn run_generation_with(
+    py: Python<'_>,
+    project_path: &str,
+    component: Component,
+    templates: Box<dyn TemplateSource>,
+    dry_run: bool,
+) -> PyResult<PyObject> {
+    let app_info = get_app_info(project_path)?;
+    let project_root = Path::new(project_path);
+    let inflections = load_inflections(project_root)?;
+    let options = GenerateOptions {
+        templates,
+        inflections,
+        run_migration: !dry_run && std::env::var("SKIP_MIGRATION").is_err(),
+    };
+
+    let started = Instant::now();
+    let (component_type, name) = describe_component(&component);
+    let response = PyDict::new_bound(py);
+    let file_count;
+    let _lock = lock_project(py, project_root)?;
+    let planned = loco_gen::preview_in(project_root, component.clone(), &app_info, &options)
+        .map_err(|e| PyErr::new::<ProjectError, _>(format!("Generation failed: {}", e)))?;
+    let touched: Vec<&str> = planned
+        .iter()
+        .flat_map(|file| std::iter::once(&file.path).chain(&file.injections))
+        .map(String::as_str)
+        .collect();
+    if dry_run {
+        let sandbox = DryRunSandbox::new(project_root, &touched)?;
+        let existing_files = list_project_files(&sandbox.root);
+        let generator = new_generator(&sandbox.root.to_string_lossy());
+        let result = loco_gen::generate_with_options(&generator, component, &app_info, &options)
+            .map_err(|e| PyErr::new::<ProjectError, _>(format!("Generation failed: {}", e)))?;
+
+        let previews = PyDict::new_bound(py);
+        let mut would_create = Vec::new();
+        for path in list_project_files(&sandbox.root) {
+            let Ok(relative) = path.strip_prefix(&sandbox.root) else {
+                continue;
+            };
+            let target = project_root.join(relative);
+            let content = fs::read_to_string(&path).unwrap_or_default();
+            if !existing_files.contains(&path) {
+                would_create.push(target.to_string_lossy().to_string());
+            } else if fs::read_to_string(&target).ok().as_deref() == Some(content.as_str()) {
+                continue;
+            }
+            previews.set_item(target.to_string_lossy().to_string(), content)?;
+        }
+        would_create.sort();
+        file_count = would_create.len();
+
+        response.set_item("success", true)?;
+        response.set_item("dry_run", true)?;
+        response.set_item("created_files", would_create)?;
+        response.set_item("previews", previews)?;
+        response.set_item("messages", loco_gen::collect_messages(&result))?;
+    } else {
+        let (existing_files, existing_dirs) = list_project_entries(project_root);
+        let originals = read_existing_files(project_root, &touched);
+        let result = match loco_gen::generate_with_options(&new_generator(project_path), component, &app_info, &options) {
+            Ok(result) => result,
+            Err(e) => {
+                let removed = roll_back_generation(project_root, &existing_files, &existing_dirs, &originals);
+                let rolled_back = if removed.is_empty() {
+                    String::new()
+                } else {
+                    format!(" (removed created files: {})", removed.join(", "))
+                };
+                return Err(PyErr::new::<ProjectError, _>(format!(
+                    "Generation failed: {}{}",
+                    e, rolled_back
+                )));
+            }
+        };
+
+        let created = created_files(project_root, &existing_files);
+        file_count = created.len();
+
+        response.set_item("success", true)?;
+        response.set_item("created_files", created)?;
+        response.set_item("messages", loco_gen::collect_messages(&result))?;
+    }
+
+    report_generation(component_type, &name, file_count, dry_run, started.elapsed());
+
+    Ok(response.into())
+}
+
+/// Template overrides of the project, as `cargo loco generate` reads them
+fn project_templates(project_root: &Path) -> FileSystemTemplateSource {
+    FileSystemTemplateSource::new(project_root.join(loco_gen::template::DEFAULT_LOCAL_TEMPLATE))
+}
+
+/// The project's template overrides, with one template replaced
+struct OverlayTemplateSource {
+    project: FileSystemTemplateSource,
+    /// Built-in template replaced, e.g. `migration/empty.t`
+    path: &'static Path,
+    content: String,
+}
+
+impl TemplateSource for OverlayTemplateSource {
+    fn find(&self, path: &Path) -> loco_gen::Result<Option<(PathBuf, String)>> {
+        if path == self.path {
+            return Ok(Some((self.path.to_path_buf(), self.content.clone())));
+        }
+        self.project.find(path)
+    }
+}
+
+/// Emit the event for a completed generation
+fn report_generation(component_type: &str, name: &str, file_count: usize, dry_run: bool, duration: Duration) {
+    tracing::info!(
+        component_type,
+        name,
+        file_count,
+        dry_run,
+        duration_ms = duration.as_millis() as u64,
+        "generation completed"
+    );
+}
+
+/// Component type and name, as reported in the generation event
+fn describe_component(component: &Component) -> (&'static str, String) {
+    match component {
+        Component::Model { name, .. } => ("model", name.clone()),
+        Component::Scaffold { name, .. } => ("scaffold", name.clone()),
+        Component::Controller { name, .. } => ("controller", name.clone()),
+        Component::Migration { name, .. } => ("migration", name.clone()),
+        _ => ("other", String::new()),
+    }
+}
+
+/// The project's inflections, see [`naming::project_inflections`]
+fn load_inflections(project_root: &Path) -> PyResult<Inflections> {
+    naming::project_inflections(project_root)
+        .map_err(|e| PyErr::new::<FileOperationError, _>(format!("Failed to load inflections: {}", e)))
+}
+
+/// Take the project's generation lock, without holding the GIL while waiting
+fn lock_project(py: Python<'_>, project_root: &Path) -> PyResult<ProjectLock> {
+    py.allow_threads(|| ProjectLock::acquire(project_root))
+        .map_err(PyErr::new::<FileOperationError, _>)
+}
+
+/// Scratch copy of the files a dry run touches, removed on drop
+struct DryRunSandbox {
+    root: PathBuf,
+}
+
+impl DryRunSandbox {
+    /// Copy the given project files, relative to the project root, which
+    /// exist
+    fn new(project_root: &Path, files: &[&str]) -> PyResult<Self> {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+        let root = std::env::temp_dir().join(format!("loco-dry-run-{}-{}", std::process::id(), nanos));
+        let sandbox = Self { root };
+
+        let prepare_failed = |e: std::io::Error| {
+            PyErr::new::<FileOperationError, _>(format!("Failed to prepare dry run: {}", e))
+        };
+        fs::create_dir_all(&sandbox.root).map_err(prepare_failed)?;
+        for relative in files {
+            let file = project_root.join(relative);
+            if !file.is_file() {
+                continue;
+            }
+            let target = sandbox.root.join(relative);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(prepare_failed)?;
+            }
+            fs::copy(&file, &target).map_err(prepare_failed)?;
+        }
+
+        Ok(sandbox)
+    }
+}
+
+impl Drop for DryRunSandbox {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
 /// Helper function to extract app info from Cargo.toml
 fn get_app_info(project_path: &str) -> PyResult<AppInfo> {
     use std::path::Path;
@@ -222,56 +1484,316 @@ fn get_app_info(project_path: &str) -> PyResult<AppInfo> {
 
 /// Execute database migration
 ///
+/// Runs `cargo loco db migrate` in the project and waits for it to finish.
+///
 /// Args:
 ///     project_path (str): Path to the Loco project root
-///     environment (str, optional): Environment name (default: from env)
-///     approvals (list): List of required approvals
-///     timeout_seconds (int): Timeout in seconds (default: 60)
-///     dependencies (list): List of dependencies
+///     environment (str, optional): Environment name, passed as `LOCO_ENV` (default: from env)
+///     approvals (list): Approvals, in the order required by `loco.operations.toml`
+///     timeout_seconds (int): Timeout in seconds, within the policy range (default: 60)
+///     dependencies (list): Dependencies, must include those required by the policy
+///     idempotency_key (str, optional): Replays the recorded result of an earlier
+///         completed call with the same key instead of running again
+///
+/// Returns:
+///     dict: Execution result with success status, the runner's output as
+///         messages, and a checksum of the applied migrations
+#[pyfunction]
+#[pyo3(signature = (project_path, approvals, dependencies, environment = None, timeout_seconds = None, idempotency_key = None))]
+fn migrate_db(
+    py: Python<'_>,
+    project_path: &str,
+    approvals: Vec<String>,
+    dependencies: Vec<String>,
+    environment: Option<String>,
+    timeout_seconds: Option<u32>,
+    idempotency_key: Option<String>,
+) -> PyResult<PyObject> {
+    let (policy, timeout) = enforce_policy(project_path, "migrate_db", &approvals, &dependencies, timeout_seconds, 60)?;
+
+    let project_root = Path::new(project_path);
+    if !project_root.join("Cargo.toml").exists() {
+        return Err(PyErr::new::<ProjectError, _>(
+            format!("Not a Loco project: Cargo.toml not found at {}", project_root.display())
+        ));
+    }
+
+    run_idempotent(py, project_root, "migrate_db", idempotency_key.as_deref(), policy.idempotency_window(), |py| {
+        let mut command = Command::new("cargo");
+        command.args(["loco", "db", "migrate"]).current_dir(project_root);
+        if let Some(environment) = &environment {
+            command.env("LOCO_ENV", environment);
+        }
+
+        let timeout = Duration::from_secs(timeout.into());
+        let output = run_operation(py, "migrate_db", timeout, move || run_command(command, timeout))?;
+        let mut messages: Vec<String> = output
+            .stdout
+            .lines()
+            .chain(output.stderr.lines())
+            .filter(|line| !line.trim().is_empty())
+            .map(str::to_string)
+            .collect();
+        if !output.success {
+            return Err(PyErr::new::<ProjectError, _>(
+                format!("`cargo loco db migrate` failed:\n{}", messages.join("\n"))
+            ));
+        }
+
+        let migrations = migration_names(project_root)?;
+        messages.push("Database migration completed successfully".to_string());
+
+        Ok(serde_json::json!({
+            "success": true,
+            "messages": messages,
+            "checksum": format!("migrate_{}", checksum(&migrations)),
+            "migrations": migrations,
+        }))
+    })
+}
+
+/// Check a privileged operation call against the project's operation policy
 ///
-/// Returns:
-///     dict: Execution result with success status and messages
-#[pyfunction]
-#[pyo3(signature = (project_path, approvals, dependencies, environment = None, timeout_seconds = None))]
-fn migrate_db(
-    py: Python<'_>,
+/// Returns the policy along with the timeout to use for the operation.
+fn enforce_policy(
     project_path: &str,
-    approvals: Vec<String>,
-    dependencies: Vec<String>,
-    environment: Option<String>,
+    operation: &str,
+    approvals: &[String],
+    dependencies: &[String],
     timeout_seconds: Option<u32>,
+    default_timeout: u32,
+) -> PyResult<(OperationPolicy, u32)> {
+    let policy = OperationPolicy::load(Path::new(project_path), operation)
+        .map_err(PyErr::new::<ProjectError, _>)?;
+    let timeout = policy
+        .check(approvals, dependencies, timeout_seconds, default_timeout)
+        .map_err(PyErr::new::<ValidationError, _>)?;
+    Ok((policy, timeout))
+}
+
+/// Run a privileged operation at most once per idempotency key
+///
+/// Without a key the operation always runs. With a key, a call completed
+/// within `window` is not run again; its recorded result is returned with
+/// `replayed` set instead. The project is locked from the lookup until the
+/// result is recorded, so concurrent calls with one key run it once.
+fn run_idempotent(
+    py: Python<'_>,
+    project_root: &Path,
+    operation: &str,
+    idempotency_key: Option<&str>,
+    window: Duration,
+    body: impl FnOnce(Python<'_>) -> PyResult<serde_json::Value>,
 ) -> PyResult<PyObject> {
-    // Validate approvals
-    let required_approvals = vec!["ops_lead".to_string(), "security_officer".to_string()];
-    if approvals != required_approvals {
-        return Err(PyErr::new::<ValidationError, _>(
-            "approvals must follow required order: ['ops_lead', 'security_officer']"
-        ));
+    let Some(key) = idempotency_key else {
+        return json_to_py(py, &body(py)?);
+    };
+
+    let _lock = lock_project(py, project_root)?;
+    let mut ledger = Ledger::open(project_root).map_err(PyErr::new::<FileOperationError, _>)?;
+    if let Some(recorded) = ledger.lookup(operation, key, window) {
+        let mut result = recorded.clone();
+        result["replayed"] = true.into();
+        return json_to_py(py, &result);
     }
-    
-    // Validate timeout
-    let timeout = timeout_seconds.unwrap_or(60);
-    if timeout < 10 || timeout > 300 {
-        return Err(PyErr::new::<ValidationError, _>(
-            "timeout_seconds must be between 10 and 300"
-        ));
+
+    let result = body(py)?;
+    ledger
+        .record(operation, key, result.clone(), window)
+        .map_err(PyErr::new::<FileOperationError, _>)?;
+
+    let mut result = result;
+    result["replayed"] = false.into();
+    json_to_py(py, &result)
+}
+
+/// Convert an operation result to Python objects
+fn json_to_py(py: Python<'_>, value: &serde_json::Value) -> PyResult<PyObject> {
+    use serde_json::Value;
+
+    Ok(match value {
+        Value::Null => py.None(),
+        Value::Bool(b) => b.into_py(py),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => i.into_py(py),
+            None => n.as_f64().unwrap_or_default().into_py(py),
+        },
+        Value::String(s) => s.into_py(py),
+        Value::Array(items) => {
+            let items = items.iter().map(|item| json_to_py(py, item)).collect::<PyResult<Vec<_>>>()?;
+            PyList::new_bound(py, items).into()
+        }
+        Value::Object(map) => {
+            let dict = PyDict::new_bound(py);
+            for (key, item) in map {
+                dict.set_item(key, json_to_py(py, item)?)?;
+            }
+            dict.into()
+        }
+    })
+}
+
+/// Run the work of a privileged operation with [`run_with_timeout`], releasing
+/// the GIL while waiting for it
+fn run_operation<T: Send + 'static>(
+    py: Python<'_>,
+    operation: &str,
+    timeout: Duration,
+    work: impl FnOnce() -> PyResult<T> + Send + 'static,
+) -> PyResult<T> {
+    py.allow_threads(|| run_with_timeout(operation, timeout, work))
+}
+
+/// Run an operation on a worker thread, failing if it runs longer than `timeout`
+///
+/// A worker that overruns is left to finish in the background with its result
+/// discarded, so operations that can hang must also stop themselves, as
+/// [`run_command`] does.
+fn run_with_timeout<T: Send + 'static>(
+    operation: &str,
+    timeout: Duration,
+    f: impl FnOnce() -> PyResult<T> + Send + 'static,
+) -> PyResult<T> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::Builder::new()
+        .name(format!("loco-{}", operation))
+        .spawn(move || {
+            let _ = sender.send(f());
+        })
+        .map_err(|e| PyErr::new::<ProjectError, _>(format!("Failed to start {}: {}", operation, e)))?;
+
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => Err(PyErr::new::<ProjectError, _>(
+            format!("{} timed out after {} seconds", operation, timeout.as_secs())
+        )),
+        Err(RecvTimeoutError::Disconnected) => Err(PyErr::new::<ProjectError, _>(
+            format!("{} stopped without a result", operation)
+        )),
     }
-    
-    // Validate dependencies
-    let required_deps = vec!["postgres".to_string(), "redis".to_string()];
-    if dependencies != required_deps {
-        return Err(PyErr::new::<ValidationError, _>(
-            "dependencies must include: ['postgres', 'redis']"
-        ));
+}
+
+/// Convert Python data (None, bool, int, float, str, list, tuple, dict) to JSON
+fn py_to_json(value: &Bound<'_, PyAny>) -> PyResult<serde_json::Value> {
+    use serde_json::Value;
+
+    if value.is_none() {
+        Ok(Value::Null)
+    } else if let Ok(b) = value.downcast::<PyBool>() {
+        Ok(b.is_true().into())
+    } else if let Ok(i) = value.extract::<i64>() {
+        Ok(i.into())
+    } else if let Ok(f) = value.extract::<f64>() {
+        Ok(f.into())
+    } else if let Ok(s) = value.extract::<String>() {
+        Ok(s.into())
+    } else if let Ok(dict) = value.downcast::<PyDict>() {
+        dict.iter()
+            .map(|(key, item)| Ok((key.extract::<String>()?, py_to_json(&item)?)))
+            .collect::<PyResult<serde_json::Map<_, _>>>()
+            .map(Value::Object)
+    } else if value.downcast::<PyList>().is_ok() || value.downcast::<PyTuple>().is_ok() {
+        value
+            .iter()?
+            .map(|item| py_to_json(&item?))
+            .collect::<PyResult<Vec<_>>>()
+            .map(Value::Array)
+    } else {
+        Err(PyErr::new::<ValidationError, _>(format!(
+            "Unsupported value {} of type {}",
+            value,
+            value.get_type().name()?
+        )))
     }
-    
-    // For now, simulate the migration (actual implementation would call Rust CLI)
-    let response = PyDict::new_bound(py);
-    response.set_item("success", true)?;
-    response.set_item("messages", vec!["Database migration completed successfully"])?;
-    response.set_item("checksum", "migrate_abc123")?;
-    
-    Ok(response.into())
+}
+
+/// Captured output of a finished command
+struct CommandOutput {
+    success: bool,
+    stdout: String,
+    stderr: String,
+}
+
+/// Run a command, killing it if it runs longer than `timeout`
+fn run_command(mut command: Command, timeout: Duration) -> PyResult<CommandOutput> {
+    let program = command.get_program().to_string_lossy().to_string();
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| PyErr::new::<ProjectError, _>(format!("Failed to run {}: {}", program, e)))?;
+
+    // drain both pipes while waiting so a chatty process cannot block on a full pipe
+    let stdout = child.stdout.take().map(read_in_background);
+    let stderr = child.stderr.take().map(read_in_background);
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(PyErr::new::<ProjectError, _>(
+                    format!("{} timed out after {} seconds", program, timeout.as_secs())
+                ));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(e) => {
+                return Err(PyErr::new::<ProjectError, _>(
+                    format!("Failed to wait for {}: {}", program, e)
+                ));
+            }
+        }
+    };
+
+    let collect = |reader: Option<std::thread::JoinHandle<String>>| {
+        reader.map(|handle| handle.join().unwrap_or_default()).unwrap_or_default()
+    };
+    Ok(CommandOutput {
+        success: status.success(),
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+    })
+}
+
+fn read_in_background<R: Read + Send + 'static>(mut reader: R) -> std::thread::JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut buf = String::new();
+        let _ = reader.read_to_string(&mut buf);
+        buf
+    })
+}
+
+/// Names of the project's migrations (`migration/src/m*.rs`), sorted
+fn migration_names(project_root: &Path) -> PyResult<Vec<String>> {
+    let migration_dir = project_root.join("migration").join("src");
+    let entries = match fs::read_dir(&migration_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(PyErr::new::<FileOperationError, _>(
+                format!("Failed to read {}: {}", migration_dir.display(), e)
+            ));
+        }
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .filter_map(|path| path.file_stem().map(|stem| stem.to_string_lossy().to_string()))
+        .filter(|stem| stem.strip_prefix('m').is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit())))
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+/// Short hex digest identifying a set of names
+fn checksum(names: &[String]) -> String {
+    let digest = Sha256::digest(names.join("\n").as_bytes());
+    digest.iter().take(6).map(|byte| format!("{:02x}", byte)).collect()
 }
 
 /// Rotate service account keys
@@ -279,14 +1801,16 @@ fn migrate_db(
 /// Args:
 ///     project_path (str): Path to the Loco project root
 ///     environment (str, optional): Environment name (default: from env)
-///     approvals (list): List of required approvals
-///     timeout_seconds (int): Timeout in seconds (default: 300)
-///     dependencies (list): List of dependencies
+///     approvals (list): Approvals, in the order required by `loco.operations.toml`
+///     timeout_seconds (int): Timeout in seconds, within the policy range (default: 300)
+///     dependencies (list): Dependencies, must include those required by the policy
+///     idempotency_key (str, optional): Replays the recorded result of an earlier
+///         completed call with the same key instead of running again
 ///
 /// Returns:
 ///     dict: Execution result with success status and messages
 #[pyfunction]
-#[pyo3(signature = (project_path, approvals, dependencies, environment = None, timeout_seconds = None))]
+#[pyo3(signature = (project_path, approvals, dependencies, environment = None, timeout_seconds = None, idempotency_key = None))]
 fn rotate_keys(
     py: Python<'_>,
     project_path: &str,
@@ -294,38 +1818,23 @@ fn rotate_keys(
     dependencies: Vec<String>,
     environment: Option<String>,
     timeout_seconds: Option<u32>,
+    idempotency_key: Option<String>,
 ) -> PyResult<PyObject> {
-    // Validate approvals
-    let required_approvals = vec!["security_officer".to_string(), "cto".to_string()];
-    if approvals != required_approvals {
-        return Err(PyErr::new::<ValidationError, _>(
-            "approvals must follow required order: ['security_officer', 'cto']"
-        ));
-    }
-    
-    // Validate timeout
-    let timeout = timeout_seconds.unwrap_or(300);
-    if timeout < 10 || timeout > 300 {
-        return Err(PyErr::new::<ValidationError, _>(
-            "timeout_seconds must be between 10 and 300"
-        ));
-    }
-    
-    // Validate dependencies
-    let required_deps = vec!["kms".to_string()];
-    if dependencies != required_deps {
-        return Err(PyErr::new::<ValidationError, _>(
-            "dependencies must include: ['kms']"
-        ));
-    }
-    
+    let (policy, timeout) = enforce_policy(project_path, "rotate_keys", &approvals, &dependencies, timeout_seconds, 300)?;
+
+    run_idempotent(py, Path::new(project_path), "rotate_keys", idempotency_key.as_deref(), policy.idempotency_window(), |py| {
+        run_operation(py, "rotate_keys", Duration::from_secs(timeout.into()), rotate_service_keys)
+    })
+}
+
+/// Work of rotate_keys
+fn rotate_service_keys() -> PyResult<serde_json::Value> {
     // For now, simulate the key rotation (actual implementation would call Rust CLI)
-    let response = PyDict::new_bound(py);
-    response.set_item("success", true)?;
-    response.set_item("messages", vec!["Key rotation completed successfully"])?;
-    response.set_item("checksum", "rotate_def456")?;
-    
-    Ok(response.into())
+    Ok(serde_json::json!({
+        "success": true,
+        "messages": ["Key rotation completed successfully"],
+        "checksum": "rotate_def456",
+    }))
 }
 
 /// Clean temporary files
@@ -333,14 +1842,16 @@ fn rotate_keys(
 /// Args:
 ///     project_path (str): Path to the Loco project root
 ///     environment (str, optional): Environment name (default: from env)
-///     approvals (list): List of required approvals
-///     timeout_seconds (int): Timeout in seconds (default: 60)
-///     dependencies (list): List of dependencies
+///     approvals (list): Approvals, in the order required by `loco.operations.toml`
+///     timeout_seconds (int): Timeout in seconds, within the policy range (default: 60)
+///     dependencies (list): Dependencies, must include those required by the policy
+///     idempotency_key (str, optional): Replays the recorded result of an earlier
+///         completed call with the same key instead of running again
 ///
 /// Returns:
 ///     dict: Execution result with success status and messages
 #[pyfunction]
-#[pyo3(signature = (project_path, approvals, dependencies, environment = None, timeout_seconds = None))]
+#[pyo3(signature = (project_path, approvals, dependencies, environment = None, timeout_seconds = None, idempotency_key = None))]
 fn clean_temp(
     py: Python<'_>,
     project_path: &str,
@@ -348,66 +1859,91 @@ fn clean_temp(
     dependencies: Vec<String>,
     environment: Option<String>,
     timeout_seconds: Option<u32>,
+    idempotency_key: Option<String>,
 ) -> PyResult<PyObject> {
-    // Validate approvals
-    let required_approvals = vec!["ops_lead".to_string()];
-    if approvals != required_approvals {
-        return Err(PyErr::new::<ValidationError, _>(
-            "approvals must follow required order: ['ops_lead']"
-        ));
-    }
-
-    // Validate timeout
-    let timeout = timeout_seconds.unwrap_or(60);
-    if timeout < 10 || timeout > 300 {
-        return Err(PyErr::new::<ValidationError, _>(
-            "timeout_seconds must be between 10 and 300"
-        ));
-    }
+    let (policy, timeout) = enforce_policy(project_path, "clean_temp", &approvals, &dependencies, timeout_seconds, 60)?;
 
-    // Validate dependencies
-    let required_deps = vec!["fs-local".to_string()];
-    if dependencies != required_deps {
-        return Err(PyErr::new::<ValidationError, _>(
-            "dependencies must include: ['fs-local']"
-        ));
-    }
+    run_idempotent(py, Path::new(project_path), "clean_temp", idempotency_key.as_deref(), policy.idempotency_window(), |py| {
+        run_operation(py, "clean_temp", Duration::from_secs(timeout.into()), clean_temp_files)
+    })
+}
 
+/// Work of clean_temp
+fn clean_temp_files() -> PyResult<serde_json::Value> {
     // For now, simulate the cleanup (actual implementation would call Rust CLI)
-    let response = PyDict::new_bound(py);
-    response.set_item("success", true)?;
-    response.set_item("messages", vec!["Temporary files cleaned successfully"])?;
-    response.set_item("checksum", "clean_ghi789")?;
-
-    Ok(response.into())
+    Ok(serde_json::json!({
+        "success": true,
+        "messages": ["Temporary files cleaned successfully"],
+        "checksum": "clean_ghi789",
+    }))
 }
 
 /// Create a new Loco project
 ///
 /// Args:
 ///     project_name (str): Name of the project (e.g., "my_app", "user_service")
-///     template_type (str): Type of template ("saas", "rest_api", "lightweight")
+///     template_type (str): Type of template ("saas", "rest_api", "lightweight", "cli")
 ///     destination_path (str): Directory where project will be created
 ///     database_type (str, optional): Database configuration ("sqlite", "postgresql", "none")
 ///     background_worker (str, optional): Background worker setup ("redis", "postgresql", "sqlite", "none")
 ///     asset_serving (str, optional): Static asset serving ("local", "cloud", "none")
+///     extra_dependencies (dict, optional): Crate name to version (e.g. "1.0") or inline
+///         table (e.g. '{ version = "1", features = ["v4"] }'), merged into
+///         `[dependencies]`; entries replace the default line for the same crate
 ///
 /// Returns:
 ///     dict: Creation result with success status, created files, and messages
 #[pyfunction]
-#[pyo3(signature = (project_name, template_type, destination_path, database_type = None, background_worker = None, asset_serving = None))]
+#[pyo3(signature = (project_name, template_type, destination_path, database_type = None, background_worker = None, asset_serving = None, extra_dependencies = None))]
 fn create_project(
-    py: Python<'_>,
     project_name: &str,
     template_type: &str,
     destination_path: &str,
     database_type: Option<String>,
     background_worker: Option<String>,
     asset_serving: Option<String>,
+    extra_dependencies: Option<&Bound<'_, PyDict>>,
 ) -> PyResult<PyObject> {
-    use std::path::Path;
+    let options = ProjectOptions {
+        database_type,
+        background_worker,
+        asset_serving,
+        extra_dependencies: match extra_dependencies {
+            Some(deps) => extract_dependencies(deps)?,
+            None => Vec::new(),
+        },
+    };
+    let (created_files, messages) = create_project_files(project_name, template_type, destination_path, options)?;
+
+    // Convert result to Python dict
+    Python::with_gil(|py| {
+        let response = PyDict::new_bound(py);
+        response.set_item("success", true)?;
+        response.set_item("created_files", created_files)?;
+        response.set_item("messages", messages)?;
+        Ok(response.into())
+    })
+}
+
+/// Settings of a new project left to the caller, `None` taking the template's default
+struct ProjectOptions {
+    database_type: Option<String>,
+    background_worker: Option<String>,
+    asset_serving: Option<String>,
+    extra_dependencies: Vec<(String, String)>,
+}
+
+/// Write a new project, returning the created files and messages
+fn create_project_files(
+    project_name: &str,
+    template_type: &str,
+    destination_path: &str,
+    options: ProjectOptions,
+) -> PyResult<(Vec<String>, Vec<String>)> {
     use std::fs;
 
+    let ProjectOptions { database_type, background_worker, asset_serving, extra_dependencies } = options;
+
     // Validate project name
     if project_name.is_empty() {
         return Err(PyErr::new::<ValidationError, _>(
@@ -415,25 +1951,24 @@ fn create_project(
         ));
     }
 
-    // Check if project name matches pattern (snake_case)
-    let name_pattern = regex::Regex::new(r"^[a-z][a-z0-9_]*$").unwrap();
-    if !name_pattern.is_match(project_name) {
+    // Check if project name is snake_case
+    if !naming::is_snake_case(project_name) {
         return Err(PyErr::new::<ValidationError, _>(
-            format!("Invalid project name '{}'. Must start with lowercase letter and contain only lowercase letters, numbers, and underscores", project_name)
+            format!("Invalid project name '{}'. Must start with lowercase letter and contain only lowercase letters, numbers, and underscores (e.g. '{}')", project_name, naming::to_snake_case(project_name))
         ));
     }
 
     // Validate template type
-    let valid_templates = vec!["saas", "rest_api", "lightweight"];
+    let valid_templates = ["saas", "rest_api", "lightweight", "cli"];
     if !valid_templates.contains(&template_type) {
         return Err(PyErr::new::<ValidationError, _>(
-            format!("Invalid template_type '{}'. Must be one of: saas, rest_api, lightweight", template_type)
+            format!("Invalid template_type '{}'. Must be one of: saas, rest_api, lightweight, cli", template_type)
         ));
     }
 
     // Validate database type
     if let Some(db) = &database_type {
-        let valid_databases = vec!["sqlite", "postgresql", "none"];
+        let valid_databases = ["sqlite", "postgresql", "none"];
         if !valid_databases.contains(&db.as_str()) {
             return Err(PyErr::new::<ValidationError, _>(
                 format!("Invalid database_type '{}'. Must be one of: sqlite, postgresql, none", db)
@@ -443,7 +1978,7 @@ fn create_project(
 
     // Validate background worker
     if let Some(worker) = &background_worker {
-        let valid_workers = vec!["redis", "postgresql", "sqlite", "none"];
+        let valid_workers = ["redis", "postgresql", "sqlite", "none"];
         if !valid_workers.contains(&worker.as_str()) {
             return Err(PyErr::new::<ValidationError, _>(
                 format!("Invalid background_worker '{}'. Must be one of: redis, postgresql, sqlite, none", worker)
@@ -453,7 +1988,7 @@ fn create_project(
 
     // Validate asset serving
     if let Some(asset) = &asset_serving {
-        let valid_assets = vec!["local", "cloud", "none"];
+        let valid_assets = ["local", "cloud", "none"];
         if !valid_assets.contains(&asset.as_str()) {
             return Err(PyErr::new::<ValidationError, _>(
                 format!("Invalid asset_serving '{}'. Must be one of: local, cloud, none", asset)
@@ -486,6 +2021,7 @@ fn create_project(
         "saas" => ("postgresql".to_string(), "redis".to_string(), "local".to_string()),
         "rest_api" => ("postgresql".to_string(), "none".to_string(), "none".to_string()),
         "lightweight" => ("sqlite".to_string(), "none".to_string(), "none".to_string()),
+        "cli" => ("none".to_string(), "none".to_string(), "none".to_string()),
         _ => ("none".to_string(), "none".to_string(), "none".to_string()),
     };
 
@@ -503,7 +2039,7 @@ fn create_project(
 
     // Create Cargo.toml
     tracing::debug!("Creating Cargo.toml with database: {}", final_db);
-    let cargo_toml_content = generate_cargo_toml(project_name, &final_db);
+    let cargo_toml_content = generate_cargo_toml(project_name, template_type, &final_db, &extra_dependencies);
     let cargo_toml_path = dest_path.join("Cargo.toml");
     fs::write(&cargo_toml_path, cargo_toml_content).map_err(|e| PyErr::new::<FileOperationError, _>(
         format!("Failed to create Cargo.toml: {}", e)
@@ -526,7 +2062,7 @@ fn create_project(
     created_files.push(main_rs_path.to_string_lossy().to_string());
     tracing::debug!("Created main.rs");
 
-    let app_rs_content = generate_app_rs(project_name, template_type, &final_db, &final_worker);
+    let app_rs_content = generate_app_rs(project_name, template_type);
     let app_rs_path = src_dir.join("app.rs");
     fs::write(&app_rs_path, app_rs_content).map_err(|e| PyErr::new::<FileOperationError, _>(
         format!("Failed to create app.rs: {}", e)
@@ -537,17 +2073,23 @@ fn create_project(
     // Add template-specific files
     match template_type {
         "saas" => {
-            create_saaS_files(&dest_path, &mut created_files, &mut messages, &final_db, &final_worker, &final_asset)?;
+            create_saaS_files(dest_path, &mut created_files, &mut messages, &final_db, &final_worker, &final_asset)?;
         }
         "rest_api" => {
-            create_api_files(&dest_path, &mut created_files, &mut messages, &final_db)?;
+            create_api_files(dest_path, &mut created_files, &mut messages, &final_db)?;
         }
         "lightweight" => {
-            // Minimal setup already created above
+            create_lightweight_files(dest_path, &mut created_files, &mut messages)?;
+        }
+        "cli" => {
+            create_cli_files(dest_path, &mut created_files, &mut messages)?;
         }
         _ => {}
     }
 
+    // Declare the modules app.rs and the controllers or tasks rely on
+    create_module_files(dest_path, template_type, &mut created_files)?;
+
     messages.push(format!("Created {} project '{}' at '{}'", template_type, project_name, destination_path));
     messages.push(format!("Database: {}", final_db));
     messages.push(format!("Background worker: {}", final_worker));
@@ -556,27 +2098,79 @@ fn create_project(
     // Log successful completion
     tracing::info!("Successfully created {} project '{}' with {} files", template_type, project_name, created_files.len());
 
-    // Convert result to Python dict
-    let response = PyDict::new_bound(py);
-    response.set_item("success", true)?;
-    response.set_item("created_files", created_files)?;
-    response.set_item("messages", messages)?;
+    Ok((created_files, messages))
+}
 
-    Ok(response.into())
+/// Read `extra_dependencies` into `(crate, TOML value)` pairs
+fn extract_dependencies(deps: &Bound<'_, PyDict>) -> PyResult<Vec<(String, String)>> {
+    let name_pattern = regex::Regex::new(r"^[A-Za-z0-9_-]+$").unwrap();
+    let mut dependencies = Vec::with_capacity(deps.len());
+    for (key, value) in deps.iter() {
+        let name: String = key.extract().map_err(|_| PyErr::new::<ValidationError, _>(
+            "extra_dependencies keys must be crate names"
+        ))?;
+        if !name_pattern.is_match(&name) {
+            return Err(PyErr::new::<ValidationError, _>(
+                format!("Invalid dependency name '{}'", name)
+            ));
+        }
+        let spec: String = value.extract().map_err(|_| PyErr::new::<ValidationError, _>(
+            format!("Dependency '{}' must be a version string or an inline table", name)
+        ))?;
+        let spec = spec.trim();
+        let toml_value = if spec.starts_with('{') {
+            toml::from_str::<toml::Table>(&format!("dependency = {}", spec)).map_err(|e| PyErr::new::<ValidationError, _>(
+                format!("Dependency '{}' is not a valid inline table: {}", name, e)
+            ))?;
+            spec.to_string()
+        } else {
+            toml::Value::String(spec.to_string()).to_string()
+        };
+        dependencies.push((name, toml_value));
+    }
+    Ok(dependencies)
 }
 
-fn generate_cargo_toml(project_name: &str, database: &str) -> String {
-    let db_dependency = match database {
-        "postgresql" => "sqlx = { version = \"0.8\", default-features = false, features = [\"postgres\", \"runtime-tokio-rustls\", \"chrono\"] }",
-        "sqlite" => "sqlx = { version = \"0.8\", default-features = false, features = [\"sqlite\", \"runtime-tokio-rustls\", \"chrono\"] }",
-        _ => "",
+fn generate_cargo_toml(project_name: &str, template_type: &str, database: &str, extra_dependencies: &[(String, String)]) -> String {
+    // the cli template runs through loco's own command line
+    let loco_dependency = match template_type {
+        "cli" => r#"{ version = "0.16", default-features = false, features = ["cli"] }"#,
+        _ => r#"{ version = "0.16", default-features = false }"#,
     };
 
-    let db_dependency_line = if !db_dependency.is_empty() {
-        format!("\n{}\n", db_dependency)
-    } else {
-        String::new()
-    };
+    let mut dependencies: Vec<(String, String)> = [
+        ("loco-rs", loco_dependency),
+        ("async-trait", r#""0.1""#),
+        ("tokio", r#"{ version = "1", features = ["full"] }"#),
+        ("serde", r#"{ version = "1.0", features = ["derive"] }"#),
+        ("serde_json", r#""1.0""#),
+        ("axum", r#""0.8""#),
+        ("tower-http", r#"{ version = "0.6", features = ["fs", "cors"] }"#),
+        ("tracing", r#""0.1""#),
+        ("tracing-subscriber", r#"{ version = "0.3", features = ["env-filter"] }"#),
+    ]
+    .into_iter()
+    .map(|(name, spec)| (name.to_string(), spec.to_string()))
+    .collect();
+
+    match database {
+        "postgresql" => dependencies.push(("sqlx".to_string(), r#"{ version = "0.8", default-features = false, features = ["postgres", "runtime-tokio-rustls", "chrono"] }"#.to_string())),
+        "sqlite" => dependencies.push(("sqlx".to_string(), r#"{ version = "0.8", default-features = false, features = ["sqlite", "runtime-tokio-rustls", "chrono"] }"#.to_string())),
+        _ => {}
+    }
+
+    // user supplied entries win over the defaults for the same crate
+    for (name, spec) in extra_dependencies {
+        match dependencies.iter_mut().find(|(existing, _)| existing == name) {
+            Some(entry) => entry.1 = spec.clone(),
+            None => dependencies.push((name.clone(), spec.clone())),
+        }
+    }
+
+    let dependency_lines: String = dependencies
+        .iter()
+        .map(|(name, spec)| format!("{} = {}\n", name, spec))
+        .collect();
 
     format!(
         r#"[package]
@@ -585,134 +2179,203 @@ version = "0.1.0"
 edition = "2021"
 
 [dependencies]
-loco = "0.16"
-tokio = {{ version = "1", features = ["full"] }}
-serde = {{ version = "1.0", features = ["derive"] }}
-serde_json = "1.0"
-axum = "0.7"
-tower-http = {{ version = "0.5", features = ["fs", "cors"] }}
-tracing = "0.1"
-tracing-subscriber = {{ version = "0.3", features = ["env-filter"] }}
 {}"#,
-        project_name, db_dependency_line
+        project_name, dependency_lines
     )
 }
 
 fn generate_main_rs(project_name: &str, template_type: &str) -> String {
     match template_type {
-        "saas" => format!(
-            r#"//! Main entry point for {} application
+        "saas" | "rest_api" => format!(
+            r#"//! Main entry point for {0} application
 
-use {}::app::App;
-use {}::boot::{run, BootResult};
+use loco_rs::{{
+    boot::{{create_app, start, ServeParams, StartMode}},
+    environment::{{resolve_from_env, Environment}},
+}};
+use {0}::app::App;
 
 #[tokio::main]
-async fn main() -> BootResult {{
-    boot::start(App::new()).await
-}}"#,
-            project_name, project_name, project_name
+async fn main() -> loco_rs::Result<()> {{
+    let environment: Environment = resolve_from_env().into();
+    let config = environment.load()?;
+    let serve_params = ServeParams {{
+        port: config.server.port,
+        binding: config.server.binding.clone(),
+    }};
+
+    let boot_result = create_app::<App>(StartMode::ServerOnly, &environment, config).await?;
+    start::<App>(boot_result, serve_params, false).await
+}}
+"#,
+            project_name
         ),
-        "rest_api" => format!(
-            r#"//! Main entry point for {} API
+        "lightweight" => format!(
+            r#"//! Main entry point for {0} lightweight service
 
-use {}::app::App;
-use {}::boot::{{run, BootResult}};
+use {0}::app;
 
 #[tokio::main]
-async fn main() -> BootResult {{
-    boot::start(App::new()).await
-}}"#,
-            project_name, project_name, project_name
+async fn main() -> std::io::Result<()> {{
+    let listener = tokio::net::TcpListener::bind(app::address()).await?;
+    println!("{0} lightweight service starting on http://{{}}", listener.local_addr()?);
+
+    axum::serve(listener, app::router()).await
+}}
+"#,
+            project_name
         ),
-        "lightweight" => format!(
-            r#"//! Main entry point for {} lightweight service
+        "cli" => format!(
+            r#"//! Command line entry point for {0}
+//!
+//! Run a task with `cargo run -- task <name>`.
 
-#[tokio::main]
-async fn main() {{
-    println!("Hello from {}!");
+use loco_rs::cli;
+use {0}::app::App;
 
-    // TODO: Implement your lightweight service here
-}}"#,
+#[tokio::main]
+async fn main() -> loco_rs::Result<()> {{
+    cli::main::<App>().await
+}}
+"#,
             project_name
         ),
         _ => format!(
-            r#"//! Main entry point for {}
+            r#"//! Main entry point for {0}
 
 fn main() {{
-    println!("Hello from {}!");
-}}"#,
+    println!("Hello from {0}!");
+}}
+"#,
             project_name
         ),
     }
 }
 
-fn generate_app_rs(project_name: &str, template_type: &str, database: &str, background_worker: &str) -> String {
+fn generate_app_rs(project_name: &str, template_type: &str) -> String {
     match template_type {
-        "saas" => format!(
-            r#"use loco::prelude::*;
-use loco::boot::CreateApp;
-use loco::controller::Routes;
+        "saas" | "rest_api" => format!(
+            r#"//! {0} application hooks
+
+use async_trait::async_trait;
+use loco_rs::{{
+    app::{{AppContext, Hooks, Initializer}},
+    bgworker::Queue,
+    boot::{{create_app, BootResult, StartMode}},
+    config::Config,
+    controller::AppRoutes,
+    environment::Environment,
+    task::Tasks,
+    Result,
+}};
 
-struct App;
+use crate::controllers;
+
+pub struct App;
 
 #[async_trait]
-impl CreateApp for App {{
-    fn create_app(_ctx: &AppContext) -> Result<Self, loco::Error> {{
-        Ok(Self)
+impl Hooks for App {{
+    fn app_name() -> &'static str {{
+        env!("CARGO_CRATE_NAME")
+    }}
+
+    fn app_version() -> String {{
+        env!("CARGO_PKG_VERSION").to_string()
+    }}
+
+    async fn boot(mode: StartMode, environment: &Environment, config: Config) -> Result<BootResult> {{
+        create_app::<Self>(mode, environment, config).await
+    }}
+
+    async fn initializers(_ctx: &AppContext) -> Result<Vec<Box<dyn Initializer>>> {{
+        Ok(vec![])
     }}
 
-    fn routes(ctx: &AppContext) -> Result<Routes, loco::Error> {{
-        Routes::new()
+    fn routes(_ctx: &AppContext) -> AppRoutes {{
+        AppRoutes::with_default_routes()
             .prefix("/api/v1")
-            .add_route(health::routes())
+            .add_route(controllers::health::routes())
+    }}
+
+    async fn connect_workers(_ctx: &AppContext, _queue: &Queue) -> Result<()> {{
+        Ok(())
     }}
-}}
 
-pub mod health;
-pub mod models;
-pub mod controllers;
+    fn register_tasks(_tasks: &mut Tasks) {{}}
+}}
 "#,
             project_name
         ),
-        "rest_api" => format!(
-            r#"use loco::prelude::*;
-use loco::boot::CreateApp;
-use loco::controller::Routes;
+        "lightweight" => format!(
+            r#"//! Lightweight {0} application
 
-struct App;
+use std::net::SocketAddr;
 
-#[async_trait]
-impl CreateApp for App {{
-    fn create_app(_ctx: &AppContext) -> Result<Self, loco::Error> {{
-        Ok(Self)
-    }}
+use axum::Router;
 
-    fn routes(ctx: &AppContext) -> Result<Routes, loco::Error> {{
-        Routes::new()
-            .prefix("/api/v1")
-            .add_route(health::routes())
-    }}
+use crate::controllers;
+
+/// Address the service listens on
+pub fn address() -> SocketAddr {{
+    SocketAddr::from(([127, 0, 0, 1], 3000))
 }}
 
-pub mod health;
-pub mod models;
-pub mod controllers;
+/// All routes served by the service
+pub fn router() -> Router {{
+    Router::new().merge(controllers::health::routes())
+}}
 "#,
             project_name
         ),
-        "lightweight" => format!(
-            r#"//! Lightweight {} application
+        "cli" => format!(
+            r#"//! {0} command line application hooks
 
-use std::net::SocketAddr;
+use async_trait::async_trait;
+use loco_rs::{{
+    app::{{AppContext, Hooks, Initializer}},
+    bgworker::Queue,
+    boot::{{create_app, BootResult, StartMode}},
+    config::Config,
+    controller::AppRoutes,
+    environment::Environment,
+    task::Tasks,
+    Result,
+}};
 
-#[tokio::main]
-async fn main() {{
-    let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
+use crate::tasks;
 
-    println!("{} lightweight service starting on http://{{}}", project_name, addr);
+pub struct App;
 
-    // TODO: Implement your service here
-    println!("Service implementation needed");
+#[async_trait]
+impl Hooks for App {{
+    fn app_name() -> &'static str {{
+        env!("CARGO_CRATE_NAME")
+    }}
+
+    fn app_version() -> String {{
+        env!("CARGO_PKG_VERSION").to_string()
+    }}
+
+    async fn boot(mode: StartMode, environment: &Environment, config: Config) -> Result<BootResult> {{
+        create_app::<Self>(mode, environment, config).await
+    }}
+
+    async fn initializers(_ctx: &AppContext) -> Result<Vec<Box<dyn Initializer>>> {{
+        Ok(vec![])
+    }}
+
+    fn routes(_ctx: &AppContext) -> AppRoutes {{
+        AppRoutes::empty()
+    }}
+
+    async fn connect_workers(_ctx: &AppContext, _queue: &Queue) -> Result<()> {{
+        Ok(())
+    }}
+
+    fn register_tasks(tasks: &mut Tasks) {{
+        tasks.register(tasks::hello::Hello);
+        // tasks-inject (do not remove)
+    }}
 }}
 "#,
             project_name
@@ -735,11 +2398,10 @@ fn create_saaS_files(
     fs::create_dir_all(&controllers_dir).map_err(|e| PyErr::new::<PyRuntimeError, _>(format!("Failed to create controllers directory: {}", e)))?;
 
     let health_controller = format!(
-        r#"use axum::{{response::IntoResponse, Json}};
-use serde_json::json;
+        r#"use loco_rs::prelude::*;
 
-pub async fn health_check() -> impl IntoResponse {{
-    Json(json!({{
+pub async fn health_check() -> Result<Response> {{
+    format::json(data!({{
         "status": "healthy",
         "service": "{}",
         "database": "{}",
@@ -748,8 +2410,8 @@ pub async fn health_check() -> impl IntoResponse {{
     }}))
 }}
 
-pub fn routes() -> axum::Router {{
-    axum::Router::new().route("/health", axum::routing::get(health_check))
+pub fn routes() -> Routes {{
+    Routes::new().add("/health", get(health_check))
 }}
 "#,
         "SaaS Service", database, background_worker, asset_serving
@@ -775,19 +2437,18 @@ fn create_api_files(
     fs::create_dir_all(&controllers_dir).map_err(|e| PyErr::new::<PyRuntimeError, _>(format!("Failed to create controllers directory: {}", e)))?;
 
     let health_controller = format!(
-        r#"use axum::{{response::IntoResponse, Json}};
-use serde_json::json;
+        r#"use loco_rs::prelude::*;
 
-pub async fn health_check() -> impl IntoResponse {{
-    Json(json!({{
+pub async fn health_check() -> Result<Response> {{
+    format::json(data!({{
         "status": "healthy",
         "service": "REST API",
         "database": "{}"
     }}))
 }}
 
-pub fn routes() -> axum::Router {{
-    axum::Router::new().route("/health", axum::routing::get(health_check))
+pub fn routes() -> Routes {{
+    Routes::new().add("/health", get(health_check))
 }}
 "#,
         database
@@ -801,6 +2462,87 @@ pub fn routes() -> axum::Router {{
     Ok(())
 }
 
+fn create_lightweight_files(
+    dest_path: &Path,
+    created_files: &mut Vec<String>,
+    messages: &mut Vec<String>,
+) -> Result<(), PyErr> {
+    let health_controller = r#"use axum::{response::IntoResponse, Json};
+use serde_json::json;
+
+pub async fn health_check() -> impl IntoResponse {
+    Json(json!({ "status": "healthy" }))
+}
+
+pub fn routes() -> axum::Router {
+    axum::Router::new().route("/health", axum::routing::get(health_check))
+}
+"#;
+    write_project_file(&dest_path.join("src/controllers/health.rs"), health_controller, created_files)?;
+
+    messages.push("Created lightweight health check".to_string());
+    Ok(())
+}
+
+fn create_cli_files(
+    dest_path: &Path,
+    created_files: &mut Vec<String>,
+    messages: &mut Vec<String>,
+) -> Result<(), PyErr> {
+    let hello_task = r#"use loco_rs::prelude::*;
+
+pub struct Hello;
+
+#[async_trait]
+impl Task for Hello {
+    fn task(&self) -> TaskInfo {
+        TaskInfo {
+            name: "hello".to_string(),
+            detail: "Print a greeting".to_string(),
+        }
+    }
+
+    async fn run(&self, _app_context: &AppContext, vars: &task::Vars) -> Result<()> {
+        let name = vars.cli_arg("name").map_or("world", String::as_str);
+        println!("Hello, {name}!");
+        Ok(())
+    }
+}
+"#;
+    write_project_file(&dest_path.join("src/tasks/hello.rs"), hello_task, created_files)?;
+
+    messages.push("Created sample task 'hello'".to_string());
+    Ok(())
+}
+
+/// Create `src/lib.rs` and the `mod.rs` files for the generated modules
+fn create_module_files(dest_path: &Path, template_type: &str, created_files: &mut Vec<String>) -> Result<(), PyErr> {
+    let src_dir = dest_path.join("src");
+    if template_type == "cli" {
+        write_project_file(&src_dir.join("lib.rs"), "pub mod app;\npub mod tasks;\n", created_files)?;
+        write_project_file(&src_dir.join("tasks/mod.rs"), "pub mod hello;\n", created_files)?;
+        return Ok(());
+    }
+    write_project_file(&src_dir.join("lib.rs"), "pub mod app;\npub mod controllers;\npub mod models;\n", created_files)?;
+    write_project_file(&src_dir.join("controllers/mod.rs"), "pub mod health;\n", created_files)?;
+    write_project_file(&src_dir.join("models/mod.rs"), "", created_files)?;
+    Ok(())
+}
+
+/// Write a file of a new project, creating its parent directory
+fn write_project_file(path: &Path, content: &str, created_files: &mut Vec<String>) -> Result<(), PyErr> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| PyErr::new::<FileOperationError, _>(
+            format!("Failed to create {}: {}", parent.display(), e)
+        ))?;
+    }
+    fs::write(path, content).map_err(|e| PyErr::new::<FileOperationError, _>(
+        format!("Failed to create {}: {}", path.display(), e)
+    ))?;
+    created_files.push(path.to_string_lossy().to_string());
+    Ok(())
+}
+
 /// Python module for loco-rs bindings
 #[pymodule]
 fn _loco_bindings(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
@@ -808,7 +2550,19 @@ fn _loco_bindings(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(generate_model, m)?)?;
     m.add_function(wrap_pyfunction!(generate_scaffold, m)?)?;
     m.add_function(wrap_pyfunction!(generate_controller_view, m)?)?;
+    m.add_function(wrap_pyfunction!(generate_migration, m)?)?;
+    m.add_function(wrap_pyfunction!(generate_alter_migration, m)?)?;
     m.add_function(wrap_pyfunction!(create_project, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_generation, m)?)?;
+    m.add_function(wrap_pyfunction!(diff_model, m)?)?;
+    m.add_function(wrap_pyfunction!(generate_seed, m)?)?;
+    m.add_function(wrap_pyfunction!(render_model_preview, m)?)?;
+    m.add_function(wrap_pyfunction!(list_components, m)?)?;
+
+    // Naming helpers
+    m.add_function(wrap_pyfunction!(to_snake_case_name, m)?)?;
+    m.add_function(wrap_pyfunction!(pluralize_name, m)?)?;
+    m.add_function(wrap_pyfunction!(singularize_name, m)?)?;
 
     // CLI utility functions
     m.add_function(wrap_pyfunction!(migrate_db, m)?)?;
@@ -822,3 +2576,95 @@ fn _loco_bindings(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+
+    /// Fields of an event, formatted with `Debug`
+    #[derive(Default)]
+    struct Fields(HashMap<String, String>);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0.insert(field.name().to_string(), format!("{:?}", value));
+        }
+    }
+
+    /// Layer keeping the fields of every event
+    #[derive(Clone, Default)]
+    struct CaptureLayer(Arc<Mutex<Vec<HashMap<String, String>>>>);
+
+    impl<S: tracing::Subscriber> Layer<S> for CaptureLayer {
+        fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+            let mut fields = Fields::default();
+            event.record(&mut fields);
+            self.0.lock().unwrap().push(fields.0);
+        }
+    }
+
+    #[test]
+    fn reports_completed_generation() {
+        let layer = CaptureLayer::default();
+        let subscriber = tracing_subscriber::registry().with(layer.clone());
+        let component = Component::Scaffold {
+            name: "post".to_string(),
+            with_tz: true,
+            fields: vec![],
+            kind: ScaffoldKind::Api,
+            with_tracing: false,
+        };
+
+        tracing::subscriber::with_default(subscriber, || {
+            let (component_type, name) = describe_component(&component);
+            report_generation(component_type, &name, 3, true, Duration::from_millis(42));
+        });
+
+        let events = layer.0.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event["message"], "generation completed");
+        assert_eq!(event["component_type"], "\"scaffold\"");
+        assert_eq!(event["name"], "\"post\"");
+        assert_eq!(event["file_count"], "3");
+        assert_eq!(event["dry_run"], "true");
+        assert_eq!(event["duration_ms"], "42");
+    }
+
+    #[test]
+    fn returns_result_within_timeout() {
+        let result = run_with_timeout("quick", Duration::from_secs(5), || Ok(42));
+        assert_eq!(result.ok(), Some(42));
+    }
+
+    #[test]
+    fn fails_once_timeout_is_exceeded() {
+        let started = Instant::now();
+        let result = run_with_timeout("slow", Duration::from_millis(50), || {
+            std::thread::sleep(Duration::from_secs(2));
+            Ok(())
+        });
+
+        assert!(result.is_err());
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn rotate_keys_times_out() {
+        pyo3::prepare_freethreaded_python();
+        let started = Instant::now();
+        let result = Python::with_gil(|py| {
+            run_operation(py, "rotate_keys", Duration::from_millis(50), || {
+                std::thread::sleep(Duration::from_secs(2));
+                rotate_service_keys()
+            })
+        });
+
+        let err = result.expect_err("rotation outlives its timeout");
+        assert!(err.to_string().contains("rotate_keys timed out"));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,56 @@
+//! Project-level generation lock
+//!
+//! Generators inject into shared files such as `migration/src/lib.rs` by
+//! reading, editing and rewriting them, so two generations running against
+//! the same project at once can lose each other's edits. Each generation holds
+//! an advisory lock on [`LOCK_FILE`] while it runs, dry runs included.
+
+use std::fs::{self, File, OpenOptions};
+use std::path::Path;
+
+/// Directory holding the bindings' state, relative to the project root. It
+/// ignores its own contents so none of it is committed.
+pub const STATE_DIR: &str = ".loco";
+
+/// Lock file, relative to the project root
+pub const LOCK_FILE: &str = ".loco/gen.lock";
+
+/// Exclusive lock on a project, released when dropped
+pub struct ProjectLock {
+    file: File,
+}
+
+impl ProjectLock {
+    /// Wait until no other generation holds the project's lock, then take it
+    pub fn acquire(project_root: &Path) -> Result<Self, String> {
+        create_state_dir(project_root)?;
+        let path = project_root.join(LOCK_FILE);
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        file.lock()
+            .map_err(|e| format!("Failed to lock {}: {}", path.display(), e))?;
+        Ok(Self { file })
+    }
+}
+
+impl Drop for ProjectLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+/// Create [`STATE_DIR`] with a `.gitignore` covering it, if missing
+fn create_state_dir(project_root: &Path) -> Result<(), String> {
+    let dir = project_root.join(STATE_DIR);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let gitignore = dir.join(".gitignore");
+    if !gitignore.exists() {
+        fs::write(&gitignore, "*\n")
+            .map_err(|e| format!("Failed to write {}: {}", gitignore.display(), e))?;
+    }
+    Ok(())
+}
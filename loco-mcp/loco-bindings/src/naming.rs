@@ -0,0 +1,96 @@
+//! Naming helpers for model, controller and project names
+//!
+//! Pluralization goes through loco-gen's inflection rules, extended with
+//! common English irregulars that the built-in rules get wrong (e.g. `people`
+//! singularizes to itself). Project overrides from `.loco-inflections.yaml`
+//! take precedence over both.
+
+use std::path::Path;
+
+use loco_gen::inflection::{Inflections, DEFAULT_INFLECTIONS_FILE};
+
+/// Irregular words, as (singular, plural)
+const IRREGULAR: &[(&str, &str)] = &[
+    ("person", "people"),
+    ("child", "children"),
+    ("man", "men"),
+    ("woman", "women"),
+    ("mouse", "mice"),
+    ("goose", "geese"),
+    ("foot", "feet"),
+    ("tooth", "teeth"),
+    ("ox", "oxen"),
+];
+
+/// Built-in irregulars as loco-gen inflection overrides, for names without a
+/// project
+pub fn builtin_inflections() -> Inflections {
+    Inflections {
+        irregular: IRREGULAR
+            .iter()
+            .map(|(singular, plural)| (singular.to_string(), plural.to_string()))
+            .collect(),
+        uncountable: Vec::new(),
+    }
+}
+
+/// Inflections used when generating into a project
+///
+/// The project's `.loco-inflections.yaml` is layered on top of the built-in
+/// irregulars.
+pub fn project_inflections(project_root: &Path) -> Result<Inflections, String> {
+    let mut inflections = builtin_inflections();
+    let path = project_root.join(DEFAULT_INFLECTIONS_FILE);
+    if path.exists() {
+        let local = Inflections::from_path(&path).map_err(|e| e.to_string())?;
+        inflections.irregular.extend(local.irregular);
+        inflections.uncountable = local.uncountable;
+    }
+    Ok(inflections)
+}
+
+/// Convert `BlogPost`, `blogPost`, `blog-post` or `Blog Post` to `blog_post`
+pub fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.trim().chars().collect();
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if matches!(c, '_' | '-' | ' ') {
+            if !snake.is_empty() && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            continue;
+        }
+        if c.is_uppercase() {
+            let prev = i.checked_sub(1).map(|j| chars[j]);
+            let next = chars.get(i + 1);
+            // `blogPost` -> `blog_post`, `HTTPServer` -> `http_server`
+            let boundary = prev.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit())
+                || (prev.is_some_and(char::is_uppercase) && next.is_some_and(|n| n.is_lowercase()));
+            if boundary && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake.trim_end_matches('_').to_string()
+}
+
+/// Whether a name is already lowercase snake_case starting with a letter
+pub fn is_snake_case(name: &str) -> bool {
+    let name_pattern = regex::Regex::new(r"^[a-z][a-z0-9_]*$").unwrap();
+    name_pattern.is_match(name)
+}
+
+/// Plural form of a name with the built-in rules, e.g. `category` ->
+/// `categories`. Use [`project_inflections`] for names in a project.
+pub fn pluralize(word: &str) -> String {
+    builtin_inflections().pluralize(word)
+}
+
+/// Singular form of a name with the built-in rules, e.g. `people` ->
+/// `person`. Use [`project_inflections`] for names in a project.
+pub fn singularize(word: &str) -> String {
+    builtin_inflections().singularize(word)
+}
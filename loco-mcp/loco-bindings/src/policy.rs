@@ -0,0 +1,130 @@
+//! Policies for the privileged operations
+//!
+//! `migrate_db`, `rotate_keys` and `clean_temp` are gated by the project's
+//! `loco.operations.toml`, with one table per operation:
+//!
+//! ```toml
+//! [migrate_db]
+//! approvals = ["ops_lead", "security_officer"]
+//! dependencies = ["postgres", "redis"]
+//! timeout_seconds = { min = 10, max = 300, default = 60 }
+//! idempotency_window_seconds = 86400
+//! ```
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// Policy file, relative to the project root
+pub const POLICY_FILE: &str = "loco.operations.toml";
+
+/// Requirements a call to one operation must meet
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OperationPolicy {
+    /// Approvals required, in this order
+    #[serde(default)]
+    pub approvals: Vec<String>,
+    /// Dependencies the caller must declare
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    /// Allowed timeout range
+    #[serde(default)]
+    pub timeout_seconds: TimeoutRange,
+    /// How long a completed call can be replayed by its idempotency key
+    #[serde(default = "default_idempotency_window")]
+    pub idempotency_window_seconds: u64,
+}
+
+fn default_idempotency_window() -> u64 {
+    24 * 60 * 60
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimeoutRange {
+    pub min: u32,
+    pub max: u32,
+    /// Used when the caller gives no timeout, falls back to the operation's own default
+    pub default: Option<u32>,
+}
+
+impl Default for TimeoutRange {
+    fn default() -> Self {
+        Self {
+            min: 10,
+            max: 300,
+            default: None,
+        }
+    }
+}
+
+impl OperationPolicy {
+    /// Load the policy for `operation` from the project's policy file
+    pub fn load(project_root: &Path, operation: &str) -> Result<Self, String> {
+        let path = project_root.join(POLICY_FILE);
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                format!(
+                    "Operation policy not found: {} (required to run {})",
+                    path.display(),
+                    operation
+                )
+            } else {
+                format!("Failed to read {}: {}", path.display(), e)
+            }
+        })?;
+
+        let mut policies: HashMap<String, Self> = toml::from_str(&content)
+            .map_err(|e| format!("Invalid operation policy {}: {}", path.display(), e))?;
+        policies
+            .remove(operation)
+            .ok_or_else(|| format!("No [{}] policy in {}", operation, path.display()))
+    }
+
+    /// Check a call against the policy, returning the timeout to use
+    pub fn check(
+        &self,
+        approvals: &[String],
+        dependencies: &[String],
+        timeout_seconds: Option<u32>,
+        default_timeout: u32,
+    ) -> Result<u32, String> {
+        if approvals != self.approvals.as_slice() {
+            return Err(format!(
+                "approvals must follow required order: {:?}",
+                self.approvals
+            ));
+        }
+
+        let range = &self.timeout_seconds;
+        let timeout = timeout_seconds
+            .or(range.default)
+            .unwrap_or(default_timeout);
+        if timeout < range.min || timeout > range.max {
+            return Err(format!(
+                "timeout_seconds must be between {} and {}",
+                range.min, range.max
+            ));
+        }
+
+        if !self
+            .dependencies
+            .iter()
+            .all(|required| dependencies.contains(required))
+        {
+            return Err(format!(
+                "dependencies must include: {:?}",
+                self.dependencies
+            ));
+        }
+
+        Ok(timeout)
+    }
+
+    pub fn idempotency_window(&self) -> Duration {
+        Duration::from_secs(self.idempotency_window_seconds)
+    }
+}
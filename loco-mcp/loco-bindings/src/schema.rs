@@ -0,0 +1,306 @@
+//! Table schemas read back from a project's migrations
+//!
+//! Loco projects describe their tables in the generated migrations, so the
+//! current columns of a table are found by replaying them in the order
+//! `migration/src/lib.rs` lists them: the `create_table` migration, then any
+//! `add_column`/`remove_column`, `alter_column` and
+//! `add_reference`/`remove_reference` calls in later ones. Only the `up` side of each migration is read.
+//!
+//! Column types are kept as the `ColType` variant the migration uses (e.g.
+//! `StringNull`), and references as their field type (`references`,
+//! `references?:author_id`), so that requested fields can be compared after
+//! going through the same mapping.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+/// Columns of a table, keyed by name
+pub type Columns = BTreeMap<String, String>;
+
+/// Current schema of a table
+#[derive(Debug)]
+pub struct TableSchema {
+    /// Migrations that define the table, in the order they apply
+    pub migrations: Vec<String>,
+    pub columns: Columns,
+}
+
+/// Difference between a table's schema and the requested fields
+#[derive(Debug, Default)]
+pub struct SchemaDiff {
+    /// Requested columns the table does not have, as (name, type)
+    pub added: Vec<(String, String)>,
+    /// Columns of the table that were not requested, as (name, type)
+    pub removed: Vec<(String, String)>,
+    /// Columns whose type differs, as (name, current type, requested type)
+    pub changed: Vec<(String, String, String)>,
+}
+
+impl SchemaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+fn patterns() -> &'static [Regex; 6] {
+    static PATTERNS: OnceLock<[Regex; 6]> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        [
+            // a column or reference entry of create_table
+            Regex::new(r#"^\(\s*"([^"]+)"\s*,\s*(ColType::(.+)|"([^"]*)")\s*\),?$"#).unwrap(),
+            Regex::new(r#"add_column\(\s*m,\s*"([^"]+)",\s*"([^"]+)",\s*ColType::(.+?)\)\.await"#).unwrap(),
+            Regex::new(r#"remove_column\(\s*m,\s*"([^"]+)",\s*"([^"]+)"\s*\)"#).unwrap(),
+            Regex::new(r#"alter_column\(\s*m,\s*"([^"]+)",\s*"([^"]+)",\s*ColType::(.+?)\)\.await"#).unwrap(),
+            Regex::new(r#"add_reference\(\s*m,\s*"([^"]+)",\s*"([^"]+)",\s*"([^"]*)"\s*\)"#).unwrap(),
+            Regex::new(r#"remove_reference\(\s*m,\s*"([^"]+)",\s*"([^"]+)",\s*"([^"]*)"\s*\)"#).unwrap(),
+        ]
+    })
+}
+
+/// Column for a reference, named and typed as in the field spec
+///
+/// Generated migrations mark nullable references with a `?` on the name.
+pub fn reference_column(name: &str, custom_field: &str) -> (String, String) {
+    let (name, nullable) = match name.strip_suffix('?') {
+        Some(name) => (name, "?"),
+        None => (name, ""),
+    };
+    let mut field_type = format!("references{}", nullable);
+    if !custom_field.is_empty() {
+        field_type.push(':');
+        field_type.push_str(custom_field);
+    }
+    (name.to_string(), field_type)
+}
+
+/// Columns the generators would create for `fields`
+pub fn columns_from_fields(fields: &[(String, String)]) -> Result<Columns, String> {
+    let (columns, references) =
+        loco_gen::get_columns_and_references(fields).map_err(|e| e.to_string())?;
+    Ok(columns
+        .into_iter()
+        .chain(
+            references
+                .iter()
+                .map(|(name, custom_field)| reference_column(name, custom_field)),
+        )
+        .collect())
+}
+
+/// Read the schema of `table` from the project's migrations
+///
+/// Returns `None` when no migration creates the table.
+pub fn load(project_root: &Path, table: &str) -> Result<Option<TableSchema>, String> {
+    let migration_dir = project_root.join("migration").join("src");
+    let mut files: Vec<_> = fs::read_dir(&migration_dir)
+        .map_err(|e| format!("Failed to read {}: {}", migration_dir.display(), e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == "rs")
+                && path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .is_some_and(|stem| {
+                        stem.starts_with('m') && stem[1..].starts_with(|c: char| c.is_ascii_digit())
+                    })
+        })
+        .collect();
+
+    // migrations apply in the order the migrator lists them, which file names
+    // do not capture when two were generated within the same second
+    let listed: Vec<String> = fs::read_to_string(migration_dir.join("lib.rs"))
+        .map(|lib| {
+            Regex::new(r"Box::new\((\w+)::Migration\)")
+                .unwrap()
+                .captures_iter(&lib)
+                .map(|caps| caps[1].to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort_by_cached_key(|path| {
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let position = listed.iter().position(|module| *module == stem).unwrap_or(usize::MAX);
+        (position, stem)
+    });
+
+    let [create_entry, add_column, remove_column, alter_column, add_reference, remove_reference] = patterns();
+    let create_call = Regex::new(&format!(
+        r#"create_table(_without_timestamps)?\(\s*m,\s*"{}""#,
+        regex::escape(table)
+    ))
+    .unwrap();
+
+    let mut schema: Option<TableSchema> = None;
+    for path in files {
+        let content =
+            fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let up = content.split("async fn down").next().unwrap_or_default();
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+
+        let Some(schema) = schema.as_mut() else {
+            let Some(create) = create_call.find(up) else {
+                continue;
+            };
+            let columns = up[create.end()..]
+                .lines()
+                .filter_map(|line| create_entry.captures(line.trim()))
+                .filter_map(|caps| {
+                    let name = &caps[1];
+                    match (caps.get(3), caps.get(4)) {
+                        (Some(_), _) if name == "id" => None,
+                        (Some(col_type), _) => Some((name.to_string(), col_type.as_str().to_string())),
+                        (None, Some(custom_field)) => Some(reference_column(name, custom_field.as_str())),
+                        (None, None) => None,
+                    }
+                })
+                .collect();
+            schema = Some(TableSchema {
+                migrations: vec![stem],
+                columns,
+            });
+            continue;
+        };
+
+        let mut changed = false;
+        for line in up.lines() {
+            if let Some(caps) = add_column
+                .captures(line)
+                .or_else(|| alter_column.captures(line))
+                .filter(|caps| &caps[1] == table)
+            {
+                schema.columns.insert(caps[2].to_string(), caps[3].to_string());
+            } else if let Some(caps) = remove_column.captures(line).filter(|caps| &caps[1] == table) {
+                schema.columns.remove(&caps[2]);
+            } else if let Some(caps) = add_reference.captures(line).filter(|caps| &caps[1] == table) {
+                let (name, field_type) = reference_column(&caps[2], &caps[3]);
+                schema.columns.insert(name, field_type);
+            } else if let Some(caps) = remove_reference.captures(line).filter(|caps| &caps[1] == table) {
+                schema.columns.remove(caps[2].trim_end_matches('?'));
+            } else {
+                continue;
+            }
+            changed = true;
+        }
+        if changed {
+            schema.migrations.push(stem);
+        }
+    }
+
+    Ok(schema)
+}
+
+/// Compare the current columns with the requested ones
+pub fn diff(current: &Columns, requested: &Columns) -> SchemaDiff {
+    let mut diff = SchemaDiff::default();
+    for (name, field_type) in requested {
+        match current.get(name) {
+            None => diff.added.push((name.clone(), field_type.clone())),
+            Some(current_type) if current_type != field_type => {
+                diff.changed
+                    .push((name.clone(), current_type.clone(), field_type.clone()));
+            }
+            Some(_) => {}
+        }
+    }
+    for (name, field_type) in current {
+        if !requested.contains_key(name) {
+            diff.removed.push((name.clone(), field_type.clone()));
+        }
+    }
+    diff
+}
+
+/// One column change in an alter migration
+#[derive(Debug, Clone)]
+pub enum Change {
+    /// Add a column, as (name, type)
+    Add(String, String),
+    /// Remove a column, as (name, type); the type is needed to add it back
+    Remove(String, String),
+    /// Change the type of a column in place, as (name, current type, new type)
+    Alter(String, String, String),
+}
+
+impl Change {
+    /// Change that undoes this one
+    pub fn inverse(&self) -> Self {
+        match self {
+            Self::Add(name, field_type) => Self::Remove(name.clone(), field_type.clone()),
+            Self::Remove(name, field_type) => Self::Add(name.clone(), field_type.clone()),
+            Self::Alter(name, from, to) => Self::Alter(name.clone(), to.clone(), from.clone()),
+        }
+    }
+
+    /// `loco_rs::schema` call making this change on `table`
+    pub fn statement(&self, table: &str) -> String {
+        let (verb, name, field_type) = match self {
+            Self::Add(name, field_type) => ("add", name, field_type),
+            Self::Remove(name, field_type) => ("remove", name, field_type),
+            Self::Alter(name, _, to) => {
+                return format!(r#"alter_column(m, "{}", "{}", ColType::{}).await?;"#, table, name, to);
+            }
+        };
+        match reference_parts(field_type) {
+            Some((nullable, custom_field)) => format!(
+                r#"{}_reference(m, "{}", "{}{}", "{}").await?;"#,
+                verb,
+                table,
+                name,
+                if nullable { "?" } else { "" },
+                custom_field
+            ),
+            None if verb == "add" => {
+                format!(r#"add_column(m, "{}", "{}", ColType::{}).await?;"#, table, name, field_type)
+            }
+            None => format!(r#"remove_column(m, "{}", "{}").await?;"#, table, name),
+        }
+    }
+}
+
+/// Whether a column of `field_type` is a reference
+pub fn is_reference(field_type: &str) -> bool {
+    reference_parts(field_type).is_some()
+}
+
+/// Nullability and custom field of a reference type, `None` for other types
+fn reference_parts(field_type: &str) -> Option<(bool, &str)> {
+    let rest = field_type.strip_prefix("references")?;
+    let (nullable, rest) = match rest.strip_prefix('?') {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+    match rest.strip_prefix(':') {
+        Some(custom_field) => Some((nullable, custom_field)),
+        None if rest.is_empty() => Some((nullable, "")),
+        None => None,
+    }
+}
+
+/// Check a column type as reported by [`diff`]
+pub fn validate_column_type(field_type: &str) -> Result<(), String> {
+    if reference_parts(field_type).is_some() {
+        return Ok(());
+    }
+
+    static SHAPE: OnceLock<Regex> = OnceLock::new();
+    let shape = SHAPE.get_or_init(|| {
+        Regex::new(r"^([A-Za-z0-9]+)(\((ArrayColType::[A-Za-z0-9]+|[0-9, ]+)\))?$").unwrap()
+    });
+    let known = shape.captures(field_type).is_some_and(|caps| {
+        let mappings = loco_gen::get_mappings();
+        mappings
+            .all_names()
+            .into_iter()
+            .any(|name| mappings.col_type_field(name).is_ok_and(|col_type| col_type == &caps[1]))
+    });
+    if known {
+        Ok(())
+    } else {
+        Err(format!("unknown column type '{}'", field_type))
+    }
+}
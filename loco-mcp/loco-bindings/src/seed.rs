@@ -0,0 +1,101 @@
+//! Seed data files
+//!
+//! `cargo loco db seed` calls the app's `Hooks::seed` with the fixtures
+//! directory, and the hook loads each table's file with `db::seed`. A seed is
+//! written as `src/fixtures/<table>.yaml` and registered by adding that call
+//! to the hook in `src/app.rs`.
+
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+/// Directory `cargo loco db seed` reads seed files from, relative to the project root
+pub const FIXTURES_DIR: &str = "src/fixtures";
+
+/// Columns of the entity generated for `table`
+///
+/// Returns `None` when the project has no entity for the table yet.
+pub fn entity_columns(project_root: &Path, table: &str) -> Result<Option<Vec<String>>, String> {
+    let path = project_root
+        .join("src")
+        .join("models")
+        .join("_entities")
+        .join(format!("{}.rs", table));
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+
+    static FIELD: OnceLock<Regex> = OnceLock::new();
+    let field = FIELD.get_or_init(|| Regex::new(r"^pub\s+(?:r#)?(\w+)\s*:").unwrap());
+    let model = content
+        .split_once("pub struct Model")
+        .and_then(|(_, rest)| rest.split_once('{'))
+        .and_then(|(_, rest)| rest.split_once('}'))
+        .map(|(body, _)| body)
+        .ok_or_else(|| format!("No `Model` struct in {}", path.display()))?;
+
+    Ok(Some(
+        model
+            .lines()
+            .filter_map(|line| field.captures(line.trim()))
+            .map(|caps| caps[1].to_string())
+            .collect(),
+    ))
+}
+
+/// Add loading `table`'s seed file to the app's `Hooks::seed`
+///
+/// Returns `false` when `src/app.rs` has no seed hook to add it to. Adding a
+/// table that is already loaded leaves the hook unchanged.
+pub fn register(project_root: &Path, table: &str) -> Result<bool, String> {
+    let path = project_root.join("src").join("app.rs");
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+
+    static HOOK: OnceLock<Regex> = OnceLock::new();
+    let hook = HOOK.get_or_init(|| {
+        Regex::new(r"async fn seed\(\s*_?ctx:\s*&AppContext,\s*_?base:\s*&Path\s*\)\s*->\s*Result<\(\)>\s*\{")
+            .unwrap()
+    });
+    let Some(signature) = hook.find(&content) else {
+        return Ok(false);
+    };
+    let file_name = format!("{}.yaml", table);
+    let body = &content[signature.end()..];
+    let Some(end) = body.find("Ok(())") else {
+        return Ok(false);
+    };
+    if body[..end].contains(&format!("\"{}\"", file_name)) {
+        return Ok(true);
+    }
+
+    let call = format!(
+        "loco_rs::db::seed::<crate::models::_entities::{}::ActiveModel>(&ctx.db, &base.join(\"{}\").display().to_string()).await?;",
+        table, file_name
+    );
+    let indent: String = body[..end]
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect();
+    let updated = format!(
+        "{}{}{}{}\n{}{}",
+        &content[..signature.start()],
+        "async fn seed(ctx: &AppContext, base: &Path) -> Result<()> {",
+        &body[..end],
+        call,
+        indent,
+        &body[end..]
+    );
+    fs::write(&path, updated).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(true)
+}
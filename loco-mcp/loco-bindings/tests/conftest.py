@@ -0,0 +1,36 @@
+"""
+Shared fixtures for the loco_bindings tests.
+"""
+
+import pytest
+
+
+MIGRATION_SRC_LIB = """#![allow(elided_lifetimes_in_paths)]
+#![allow(clippy::wildcard_imports)]
+pub use sea_orm_migration::prelude::*;
+
+pub struct Migrator;
+
+#[async_trait::async_trait]
+impl MigratorTrait for Migrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        vec![
+            // inject-above (do not remove this comment)
+        ]
+    }
+}
+"""
+
+
+@pytest.fixture
+def project(tmp_path, monkeypatch):
+    """Create a minimal Loco project and run the generators from its root."""
+    (tmp_path / "Cargo.toml").write_text('[package]\nname = "demo_app"\nversion = "0.1.0"\n')
+    (tmp_path / "migration" / "src").mkdir(parents=True)
+    (tmp_path / "migration" / "src" / "lib.rs").write_text(MIGRATION_SRC_LIB)
+    (tmp_path / "tests" / "models").mkdir(parents=True)
+    (tmp_path / "tests" / "models" / "mod.rs").write_text("")
+
+    monkeypatch.setenv("SKIP_MIGRATION", "1")
+    monkeypatch.chdir(tmp_path)
+    return tmp_path
@@ -0,0 +1,162 @@
+"""
+Tests for the files written by create_project.
+"""
+
+import os
+import subprocess
+
+import pytest
+
+try:
+    import loco_bindings
+    HAS_BINDINGS = True
+except ImportError:
+    HAS_BINDINGS = False
+    print("Warning: loco_bindings not available, skipping create_project tests")
+
+# `cargo check` on a generated project downloads and builds loco-rs, so it
+# only runs when explicitly requested
+RUN_CARGO_CHECK = bool(os.environ.get("LOCO_BINDINGS_CARGO_CHECK"))
+
+
+MODULE_FILES = [
+    "src/lib.rs",
+    "src/app.rs",
+    "src/controllers/mod.rs",
+    "src/controllers/health.rs",
+    "src/models/mod.rs",
+]
+
+
+class TestProjectModules:
+    """Test that every module referenced by the generated code exists."""
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    @pytest.mark.parametrize("template_type", ["lightweight", "rest_api"])
+    def test_referenced_modules_exist(self, tmp_path, template_type):
+        """Test that lib.rs and the controllers/models modules are created."""
+        destination = tmp_path / "my_app"
+
+        result = loco_bindings.create_project("my_app", template_type, str(destination))
+
+        for module_file in MODULE_FILES:
+            assert (destination / module_file).is_file(), module_file
+            assert str(destination / module_file) in result["created_files"]
+
+        lib_rs = (destination / "src" / "lib.rs").read_text()
+        assert "pub mod app;" in lib_rs
+        assert "pub mod controllers;" in lib_rs
+        assert "pub mod models;" in lib_rs
+        assert "pub mod health;" in (destination / "src" / "controllers" / "mod.rs").read_text()
+
+
+class TestCliProject:
+    """Test the `cli` template, which runs tasks instead of serving HTTP."""
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_tasks_scaffold(self, tmp_path):
+        """Test that the tasks module and a sample task are created."""
+        destination = tmp_path / "my_tool"
+
+        result = loco_bindings.create_project("my_tool", "cli", str(destination))
+
+        for module_file in ["src/lib.rs", "src/app.rs", "src/tasks/mod.rs", "src/tasks/hello.rs"]:
+            assert (destination / module_file).is_file(), module_file
+            assert str(destination / module_file) in result["created_files"]
+        assert not (destination / "src" / "controllers").exists()
+
+        assert "pub mod tasks;" in (destination / "src" / "lib.rs").read_text()
+        assert "pub mod hello;" in (destination / "src" / "tasks" / "mod.rs").read_text()
+        app_rs = (destination / "src" / "app.rs").read_text()
+        assert "tasks.register(tasks::hello::Hello);" in app_rs
+        assert "// tasks-inject" in app_rs
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_defaults(self, tmp_path):
+        """Test that a cli project has no database, worker or assets by default."""
+        result = loco_bindings.create_project("my_tool", "cli", str(tmp_path / "my_tool"))
+
+        assert "Database: none" in result["messages"]
+        assert "Background worker: none" in result["messages"]
+        assert "Asset serving: none" in result["messages"]
+
+
+class TestProjectCompiles:
+    """Test that generated projects build against loco-rs."""
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    @pytest.mark.skipif(not RUN_CARGO_CHECK, reason="set LOCO_BINDINGS_CARGO_CHECK=1 to run")
+    @pytest.mark.parametrize("template_type", ["saas", "rest_api", "lightweight", "cli"])
+    def test_cargo_check(self, tmp_path, template_type):
+        """Test that `cargo check` succeeds on a freshly created project."""
+        destination = tmp_path / "my_app"
+        loco_bindings.create_project("my_app", template_type, str(destination))
+
+        result = subprocess.run(
+            ["cargo", "check", "--quiet"],
+            cwd=destination,
+            capture_output=True,
+            text=True,
+        )
+
+        assert result.returncode == 0, result.stderr
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_single_main(self, tmp_path):
+        """Test that the lightweight app module does not declare a second `main`."""
+        destination = tmp_path / "my_app"
+        loco_bindings.create_project("my_app", "lightweight", str(destination))
+
+        assert "fn main()" in (destination / "src" / "main.rs").read_text()
+        assert "fn main()" not in (destination / "src" / "app.rs").read_text()
+
+
+class TestExtraDependencies:
+    """Test that extra_dependencies are merged into Cargo.toml."""
+
+    @staticmethod
+    def _dependencies(destination):
+        cargo_toml = (destination / "Cargo.toml").read_text()
+        return cargo_toml.split("[dependencies]\n", 1)[1].splitlines()
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_extra_dependency(self, tmp_path):
+        """Test that an extra dependency is added to `[dependencies]`."""
+        destination = tmp_path / "my_app"
+
+        loco_bindings.create_project(
+            "my_app",
+            "rest_api",
+            str(destination),
+            extra_dependencies={"uuid": '{ version = "1", features = ["v4"] }'},
+        )
+
+        dependencies = self._dependencies(destination)
+        assert 'uuid = { version = "1", features = ["v4"] }' in dependencies
+        assert 'loco-rs = { version = "0.16", default-features = false }' in dependencies
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_override_default(self, tmp_path):
+        """Test that a user supplied version replaces the default line."""
+        destination = tmp_path / "my_app"
+
+        loco_bindings.create_project(
+            "my_app", "rest_api", str(destination), extra_dependencies={"serde": "1.0.200"}
+        )
+
+        serde_lines = [
+            line for line in self._dependencies(destination) if line.startswith("serde ")
+        ]
+        assert serde_lines == ['serde = "1.0.200"']
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_invalid_dependency(self, tmp_path):
+        """Test that a malformed inline table is rejected before writing."""
+        destination = tmp_path / "my_app"
+
+        with pytest.raises(loco_bindings.ValidationError):
+            loco_bindings.create_project(
+                "my_app", "rest_api", str(destination), extra_dependencies={"uuid": "{ version = "}
+            )
+
+        assert not destination.exists()
@@ -0,0 +1,857 @@
+"""
+Tests for the generation functions (generate_model, generate_scaffold).
+
+These tests run the generators against a minimal project layout created in a
+temporary directory.
+"""
+
+import os
+import subprocess
+import sys
+
+import pytest
+import yaml
+
+try:
+    import loco_bindings
+    HAS_BINDINGS = True
+except ImportError:
+    HAS_BINDINGS = False
+    print("Warning: loco_bindings not available, skipping generation tests")
+
+
+class TestFieldTypeValidation:
+    """Test field type validation in generate_model."""
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_valid_field_types(self, project):
+        """Test that supported field types are accepted."""
+        result = loco_bindings.generate_model(
+            str(project),
+            "post",
+            {"title": "string!", "views": "int", "author": "references"},
+            True,
+        )
+
+        assert result["success"] is True
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_invalid_field_type(self, project):
+        """Test that an unknown field type is rejected with a suggestion."""
+        with pytest.raises(loco_bindings.ValidationError) as exc_info:
+            loco_bindings.generate_model(
+                str(project), "post", {"title": "strng"}, True
+            )
+
+        message = str(exc_info.value)
+        assert "'strng'" in message
+        assert "'title'" in message
+        assert "Did you mean 'string'?" in message
+        assert not list((project / "migration" / "src").glob("m*_posts.rs"))
+
+
+class TestCreatedFiles:
+    """Test that generation reports the files it created."""
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_generate_model_returns_created_files(self, project):
+        """Test that the created migration and model test are listed."""
+        result = loco_bindings.generate_model(
+            str(project), "post", {"title": "string"}, True
+        )
+
+        created_files = result["created_files"]
+        assert created_files
+        assert any(
+            path.startswith(str(project / "migration" / "src" / "m"))
+            and path.endswith("_posts.rs")
+            for path in created_files
+        )
+        assert str(project / "tests" / "models" / "posts.rs") in created_files
+        # files that were only injected into are not reported as created
+        assert str(project / "migration" / "src" / "lib.rs") not in created_files
+
+
+class TestRollback:
+    """Test that a failed generation is undone."""
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_failed_scaffold_rolls_back_model(self, project, monkeypatch):
+        """Test that the model files are removed when the migrator fails."""
+        (project / "src" / "controllers").mkdir(parents=True)
+        (project / "src" / "controllers" / "mod.rs").write_text("")
+        (project / "src" / "app.rs").write_text("")
+        (project / "tests" / "requests").mkdir()
+        (project / "tests" / "requests" / "mod.rs").write_text("")
+        migration_lib = (project / "migration" / "src" / "lib.rs").read_bytes()
+        models_mod = (project / "tests" / "models" / "mod.rs").read_bytes()
+
+        # the project has no `cargo loco-tool` to migrate with
+        monkeypatch.delenv("SKIP_MIGRATION")
+        with pytest.raises(loco_bindings.ProjectError) as exc_info:
+            loco_bindings.generate_scaffold(
+                str(project), "post", {"title": "string"}, "api", True
+            )
+
+        assert "removed created files" in str(exc_info.value)
+        assert not list((project / "migration" / "src").glob("m*_posts.rs"))
+        assert not (project / "tests" / "models" / "posts.rs").exists()
+        assert not (project / "src" / "models").exists()
+        assert not (project / "src" / "controllers" / "posts.rs").exists()
+        # pre-existing files are kept, without the injections
+        assert (project / "Cargo.toml").exists()
+        assert (project / "migration" / "src" / "lib.rs").read_bytes() == migration_lib
+        assert (project / "tests" / "models" / "mod.rs").read_bytes() == models_mod
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_missing_injection_target_writes_nothing(self, project):
+        """Test that a scaffold the project cannot take is refused up front."""
+        migration_lib = (project / "migration" / "src" / "lib.rs").read_bytes()
+
+        # the project has no src/app.rs to inject the controller into
+        with pytest.raises(loco_bindings.ProjectError) as exc_info:
+            loco_bindings.generate_scaffold(
+                str(project), "post", {"title": "string"}, "api", True
+            )
+
+        assert "cannot inject into src/controllers/mod.rs" in str(exc_info.value)
+        assert not list((project / "migration" / "src").glob("m*_posts.rs"))
+        assert not (project / "tests" / "models" / "posts.rs").exists()
+        assert (project / "migration" / "src" / "lib.rs").read_bytes() == migration_lib
+
+
+class TestScaffoldKind:
+    """Test the kinds accepted by the controller and scaffold generators."""
+
+    @staticmethod
+    def _add_controller_files(project):
+        (project / "src" / "controllers").mkdir(parents=True)
+        (project / "src" / "controllers" / "mod.rs").write_text("")
+        (project / "src" / "app.rs").write_text(
+            "fn routes() -> AppRoutes {\n    AppRoutes::with_default_routes()\n}\n"
+        )
+        (project / "tests" / "requests").mkdir()
+        (project / "tests" / "requests" / "mod.rs").write_text("")
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    @pytest.mark.parametrize("kind", ["api", "html", "htmx", "HTML"])
+    def test_valid_kind(self, project, kind):
+        """Test that every kind is accepted, ignoring case."""
+        self._add_controller_files(project)
+
+        result = loco_bindings.generate_controller_view(
+            str(project), "posts", ["index"], kind
+        )
+
+        assert result["success"] is True
+        assert (project / "src" / "controllers" / "posts.rs").exists()
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_invalid_kind(self, project):
+        """Test that an unknown kind lists the valid ones."""
+        with pytest.raises(loco_bindings.ValidationError) as exc_info:
+            loco_bindings.generate_scaffold(
+                str(project), "post", {"title": "string"}, "grpc", True
+            )
+
+        assert "Must be one of: api, html, htmx" in str(exc_info.value)
+
+
+class TestProjectLock:
+    """Test that generations against one project are serialized."""
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_concurrent_generations(self, project):
+        """Test that generations in parallel processes keep every injection."""
+        names = [f"item{i}" for i in range(8)]
+        env = {**os.environ, "PYTHONPATH": os.pathsep.join(sys.path)}
+        processes = [
+            subprocess.Popen(
+                [
+                    sys.executable,
+                    "-c",
+                    "import sys, loco_bindings; "
+                    "loco_bindings.generate_model(sys.argv[1], sys.argv[2], {'title': 'string'}, True)",
+                    str(project),
+                    name,
+                ],
+                env=env,
+                stdout=subprocess.DEVNULL,
+            )
+            for name in names
+        ]
+        assert all(process.wait(timeout=120) == 0 for process in processes)
+
+        migration_lib = (project / "migration" / "src" / "lib.rs").read_text()
+        models_mod = (project / "tests" / "models" / "mod.rs").read_text()
+        for name in names:
+            assert migration_lib.count(f"_{name}s::Migration),") == 1
+            assert migration_lib.count(f"_{name}s;") == 1
+            assert models_mod.count(f"mod {name}s;") == 1
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_lock_released_on_error(self, project):
+        """Test that a failed generation does not keep the project locked."""
+        with pytest.raises(loco_bindings.ProjectError):
+            loco_bindings.generate_scaffold(
+                str(project), "post", {"title": "string"}, "api", True
+            )
+
+        result = loco_bindings.generate_model(str(project), "comment", {"body": "text"}, True)
+
+        assert result["success"] is True
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_lock_file_is_ignored(self, project):
+        """Test that the lock is kept in the ignored .loco directory."""
+        loco_bindings.generate_model(
+            str(project), "post", {"title": "string"}, True, dry_run=True
+        )
+
+        assert (project / ".loco" / "gen.lock").exists()
+        assert (project / ".loco" / ".gitignore").read_text() == "*\n"
+        assert not (project / ".loco-gen.lock").exists()
+
+
+class TestTemplateOverrides:
+    """Test that templates in the project's .loco-templates are used."""
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_model_test_override(self, project, tmp_path_factory, monkeypatch):
+        """Test that an override is found from outside the project root."""
+        override = project / ".loco-templates" / "model" / "test.t"
+        override.parent.mkdir(parents=True)
+        override.write_text(
+            'to: "tests/models/{{ plural_file_name }}.rs"\n'
+            "---\n"
+            "// custom model test for {{ name }}\n"
+        )
+        monkeypatch.chdir(tmp_path_factory.mktemp("elsewhere"))
+
+        loco_bindings.generate_model(str(project), "post", {"title": "string"}, True)
+
+        content = (project / "tests" / "models" / "posts.rs").read_text()
+        assert content == "// custom model test for post\n"
+
+
+class TestDryRun:
+    """Test that dry runs render without touching the project."""
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_dry_run_writes_nothing(self, project):
+        """Test that no files appear on disk and previews are returned."""
+        def project_files():
+            # the lock lives in .loco, which dry runs may create
+            return sorted(
+                p for p in project.rglob("*") if ".loco" not in p.relative_to(project).parts
+            )
+
+        before = project_files()
+        migration_lib = (project / "migration" / "src" / "lib.rs").read_text()
+
+        result = loco_bindings.generate_model(
+            str(project), "post", {"title": "string"}, True, dry_run=True
+        )
+
+        assert result["success"] is True
+        assert result["dry_run"] is True
+        assert project_files() == before
+        assert (project / "migration" / "src" / "lib.rs").read_text() == migration_lib
+
+        model_test = str(project / "tests" / "models" / "posts.rs")
+        assert model_test in result["created_files"]
+        assert set(result["created_files"]) <= set(result["previews"])
+        # injected files are previewed with their would-be content
+        preview = result["previews"][str(project / "migration" / "src" / "lib.rs")]
+        assert "_posts::Migration" in preview
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_dry_run_reports_injection_errors(self, project):
+        """Test that a missing injection target is still an error."""
+        (project / "migration" / "src" / "lib.rs").unlink()
+
+        with pytest.raises(loco_bindings.ProjectError):
+            loco_bindings.generate_model(
+                str(project), "post", {"title": "string"}, True, dry_run=True
+            )
+
+        assert not (project / "tests" / "models" / "posts.rs").exists()
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_dry_run_does_not_migrate(self, project, monkeypatch):
+        """Test that a dry run skips the migrator without SKIP_MIGRATION."""
+        monkeypatch.delenv("SKIP_MIGRATION")
+
+        result = loco_bindings.generate_model(
+            str(project), "post", {"title": "string"}, True, dry_run=True
+        )
+
+        assert result["success"] is True
+        assert "SKIP_MIGRATION" not in os.environ
+
+
+class TestFieldConstraints:
+    """Test `type:constraint` field definitions."""
+
+    @staticmethod
+    def _migration(project):
+        return next((project / "migration" / "src").glob("m*_users.rs")).read_text()
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_unique_field(self, project):
+        """Test that a unique email field becomes a unique column."""
+        loco_bindings.generate_model(
+            str(project), "user", {"email": "string:unique"}, True
+        )
+
+        assert '("email", ColType::StringUniq)' in self._migration(project)
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_foreign_key_field(self, project):
+        """Test that a foreign key field becomes a reference to its table."""
+        loco_bindings.generate_model(
+            str(project),
+            "user",
+            {"team_id": "int:foreign_key:teams", "manager_id": "int:nullable:foreign_key:users"},
+            True,
+        )
+
+        migration = self._migration(project)
+        assert '("team", "team_id")' in migration
+        assert '("user?", "manager_id")' in migration
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_conflicting_constraints(self, project):
+        """Test that conflicting constraints are rejected before generating."""
+        with pytest.raises(loco_bindings.ValidationError) as exc_info:
+            loco_bindings.generate_model(
+                str(project), "user", {"id": "int:nullable:primary_key"}, True
+            )
+
+        assert "'nullable' and 'primary_key' conflict" in str(exc_info.value)
+        assert not list((project / "migration" / "src").glob("m*_users.rs"))
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_default_value(self, project):
+        """Test that a default value reaches the migration's column definition."""
+        loco_bindings.generate_model(
+            str(project),
+            "user",
+            {"status": "string:default:active", "logins": "int!:default:0"},
+            True,
+        )
+
+        migration = self._migration(project)
+        assert '("status", ColType::StringWithDefault("active".to_string()))' in migration
+        assert '("logins", ColType::IntegerWithDefault(0))' in migration
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_incompatible_default_value(self, project):
+        """Test that a default value must fit the field type."""
+        with pytest.raises(loco_bindings.ValidationError) as exc_info:
+            loco_bindings.generate_model(
+                str(project), "user", {"age": "int:default:abc"}, True
+            )
+
+        assert "default value `abc` is not a valid i32" in str(exc_info.value)
+        assert not list((project / "migration" / "src").glob("m*_users.rs"))
+
+        with pytest.raises(loco_bindings.ValidationError) as exc_info:
+            loco_bindings.generate_model(
+                str(project), "user", {"status": "string:nullable:default:active"}, True
+            )
+
+        assert "'nullable' columns cannot have a default" in str(exc_info.value)
+
+
+class TestValidateGeneration:
+    """Test validate_generation, which checks a spec without writing."""
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_valid_spec(self, project):
+        """Test that a valid spec succeeds and writes nothing."""
+        before = sorted(project.rglob("*"))
+
+        result = loco_bindings.validate_generation(
+            str(project), "post", {"title": "string!", "email": "string:unique"}
+        )
+
+        assert result["success"] is True
+        assert result["errors"] == []
+        assert result["created_files"] == []
+        assert sorted(project.rglob("*")) == before
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_invalid_fields(self, project):
+        """Test that every invalid field is reported."""
+        result = loco_bindings.validate_generation(
+            str(project), "post", {"title": "strng", "bio": "text:nullable:unique"}
+        )
+
+        assert result["success"] is False
+        assert [error["error_type"] for error in result["errors"]] == [
+            "ValidationError",
+            "ValidationError",
+        ]
+        assert "Did you mean 'string'?" in result["errors"][0]["message"]
+        assert "'nullable' and 'unique' conflict" in result["errors"][1]["message"]
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_existing_model(self, project):
+        """Test that a model with an existing migration is reported."""
+        loco_bindings.generate_model(str(project), "post", {"title": "string"}, True)
+
+        result = loco_bindings.validate_generation(
+            str(project), "post", {"title": "string"}
+        )
+
+        assert result["success"] is False
+        assert result["errors"][0]["error_type"] == "ProjectError"
+        assert "already exists" in result["errors"][0]["message"]
+
+
+class TestGenerateMigration:
+    """Test standalone migrations built from column operations."""
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_add_column(self, project):
+        """Test that an add_column operation becomes a single add-columns migration."""
+        result = loco_bindings.generate_migration(
+            str(project), "add_age_to_users", ["add_column:users:age:i32"]
+        )
+
+        assert result["success"] is True
+        migrations = list((project / "migration" / "src").glob("m*_add_age_to_users.rs"))
+        assert len(migrations) == 1
+        assert [str(migrations[0])] == result["created_files"]
+
+        migration = migrations[0].read_text()
+        assert 'add_column(m, "users", "age", ColType::IntegerNull).await?;' in migration
+        assert 'remove_column(m, "users", "age").await?;' in migration
+        lib_rs = (project / "migration" / "src" / "lib.rs").read_text()
+        assert f"mod {migrations[0].stem};" in lib_rs
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_drop_column(self, project):
+        """Test that a drop_column operation becomes a remove-columns migration."""
+        loco_bindings.generate_migration(
+            str(project), "remove_legacy_from_users", ["drop_column:users:legacy"]
+        )
+
+        migration = next(
+            (project / "migration" / "src").glob("m*_remove_legacy_from_users.rs")
+        ).read_text()
+        assert 'remove_column(m, "users", "legacy").await?;' in migration
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    @pytest.mark.parametrize(
+        "operations, message",
+        [
+            (["rename_column:users:age:years"], "unknown operation 'rename_column'"),
+            (["add_column:users:age"], "add_column requires a type"),
+            (["add_column:Users:age:i32"], "'Users' is not a valid table name"),
+            (["add_column:users:age:integer"], "Did you mean 'int'?"),
+            (
+                ["add_column:users:age:i32", "drop_column:users:legacy"],
+                "must share a verb and table",
+            ),
+        ],
+    )
+    def test_invalid_operation(self, project, operations, message):
+        """Test that invalid operations are rejected before writing."""
+        with pytest.raises(loco_bindings.ValidationError) as exc_info:
+            loco_bindings.generate_migration(str(project), "add_age_to_users", operations)
+
+        assert message in str(exc_info.value)
+        assert not list((project / "migration" / "src").glob("m*.rs"))
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_name_must_describe_operations(self, project):
+        """Test that a name loco-gen cannot infer the table from is rejected."""
+        with pytest.raises(loco_bindings.ValidationError) as exc_info:
+            loco_bindings.generate_migration(
+                str(project), "user_age", ["add_column:users:age:i32"]
+            )
+
+        assert "expected e.g. 'add_age_to_users'" in str(exc_info.value)
+
+
+class TestDiffModel:
+    """Test diff_model against models generated into the project."""
+
+    @staticmethod
+    def _generate_post(project):
+        loco_bindings.generate_model(
+            str(project), "post", {"title": "string", "body": "text"}, True
+        )
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_added_column(self, project):
+        """Test that a requested column the table lacks is reported as added."""
+        self._generate_post(project)
+
+        result = loco_bindings.diff_model(
+            str(project), "post", {"title": "string", "body": "text", "views": "int"}
+        )
+
+        assert result["table"] == "posts"
+        assert result["has_changes"] is True
+        assert result["added"] == [{"name": "views", "type": "IntegerNull"}]
+        assert result["removed"] == []
+        assert result["changed"] == []
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_removed_column(self, project):
+        """Test that a column missing from the spec is reported as removed."""
+        self._generate_post(project)
+
+        result = loco_bindings.diff_model(str(project), "post", {"title": "string"})
+
+        assert result["added"] == []
+        assert result["removed"] == [{"name": "body", "type": "TextNull"}]
+        assert result["changed"] == []
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_changed_type(self, project):
+        """Test that a column with another type or constraint is reported as changed."""
+        self._generate_post(project)
+
+        result = loco_bindings.diff_model(
+            str(project), "post", {"title": "string:required", "body": "text"}
+        )
+
+        assert result["added"] == []
+        assert result["removed"] == []
+        assert result["changed"] == [
+            {"name": "title", "from": "StringNull", "to": "String"}
+        ]
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_later_migrations_are_applied(self, project):
+        """Test that columns added and removed by later migrations are taken into account."""
+        self._generate_post(project)
+        loco_bindings.generate_migration(
+            str(project), "add_views_to_posts", ["add_column:posts:views:i32"]
+        )
+        loco_bindings.generate_migration(
+            str(project), "remove_body_from_posts", ["drop_column:posts:body"]
+        )
+
+        result = loco_bindings.diff_model(
+            str(project), "post", {"title": "string", "views": "int"}
+        )
+
+        assert result["has_changes"] is False
+        assert len(result["migrations"]) == 3
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_unknown_model(self, project):
+        """Test that diffing a model without a migration is a project error."""
+        with pytest.raises(loco_bindings.ProjectError) as exc_info:
+            loco_bindings.diff_model(str(project), "post", {"title": "string"})
+
+        assert "no migration creates table 'posts'" in str(exc_info.value)
+
+
+class TestGenerateAlterMigration:
+    """Test generate_alter_migration with diffs from diff_model."""
+
+    @staticmethod
+    def _alter_migration(project, result):
+        (path,) = result["created_files"]
+        assert path.startswith(str(project / "migration" / "src" / "m"))
+        assert path.endswith("_alter_posts.rs")
+        up, down = open(path).read().split("async fn down")
+        return up, down
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_add_column(self, project):
+        """Test that up adds the column and down drops it."""
+        loco_bindings.generate_model(str(project), "post", {"title": "string"}, True)
+        diff = loco_bindings.diff_model(
+            str(project), "post", {"title": "string", "views": "int"}
+        )
+
+        result = loco_bindings.generate_alter_migration(str(project), "post", diff)
+
+        assert result["success"] is True
+        up, down = self._alter_migration(project, result)
+        assert 'add_column(m, "posts", "views", ColType::IntegerNull).await?;' in up
+        assert 'remove_column(m, "posts", "views").await?;' in down
+        assert "_alter_posts::Migration" in (
+            project / "migration" / "src" / "lib.rs"
+        ).read_text()
+        assert "Warning" not in result["messages"]
+        assert "local templates" not in result["messages"]
+
+        after = loco_bindings.diff_model(
+            str(project), "post", {"title": "string", "views": "int"}
+        )
+        assert after["has_changes"] is False
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_changed_type_alters_column(self, project):
+        """Test that a type change alters the column in place and is reversible."""
+        loco_bindings.generate_model(
+            str(project), "post", {"title": "string", "body": "text"}, True
+        )
+        diff = loco_bindings.diff_model(str(project), "post", {"title": "string!"})
+
+        result = loco_bindings.generate_alter_migration(str(project), "post", diff)
+
+        up, down = self._alter_migration(project, result)
+        assert 'alter_column(m, "posts", "title", ColType::String).await?;' in up
+        assert 'remove_column(m, "posts", "title")' not in up
+        assert 'remove_column(m, "posts", "body")' in up
+        assert 'add_column(m, "posts", "body", ColType::TextNull)' in down
+        assert 'alter_column(m, "posts", "title", ColType::StringNull).await?;' in down
+        assert "Warning" not in result["messages"]
+
+        after = loco_bindings.diff_model(str(project), "post", {"title": "string!"})
+        assert after["has_changes"] is False
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_changed_reference_warns(self, project):
+        """Test that a reference whose type changed is re-added with a warning."""
+        loco_bindings.generate_model(
+            str(project), "post", {"title": "string", "user": "references"}, True
+        )
+        diff = loco_bindings.diff_model(
+            str(project), "post", {"title": "string", "user": "references?"}
+        )
+
+        result = loco_bindings.generate_alter_migration(str(project), "post", diff)
+
+        up, down = self._alter_migration(project, result)
+        assert up.index('remove_reference(m, "posts", "user"') < up.index(
+            'add_reference(m, "posts", "user?"'
+        )
+        assert 'add_reference(m, "posts", "user"' in down
+        assert "Warning" in result["messages"]
+        assert "`user`" in result["messages"]
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_dry_run(self, project):
+        """Test that a dry run previews the migration without writing it."""
+        loco_bindings.generate_model(str(project), "post", {"title": "string"}, True)
+        lib_rs = (project / "migration" / "src" / "lib.rs").read_text()
+        diff = loco_bindings.diff_model(
+            str(project), "post", {"title": "string", "views": "int"}
+        )
+
+        result = loco_bindings.generate_alter_migration(
+            str(project), "post", diff, dry_run=True
+        )
+
+        assert result["dry_run"] is True
+        (path,) = result["created_files"]
+        assert path.endswith("_alter_posts.rs")
+        assert "add_column(m, \"posts\", \"views\"" in result["previews"][path]
+        assert not list((project / "migration" / "src").glob("m*_alter_posts.rs"))
+        assert (project / "migration" / "src" / "lib.rs").read_text() == lib_rs
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_unknown_table(self, project):
+        """Test that the model's table must exist."""
+        with pytest.raises(loco_bindings.ProjectError):
+            loco_bindings.generate_alter_migration(
+                str(project), "post", {"added": [{"name": "views", "type": "IntegerNull"}]}
+            )
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_stale_diff(self, project):
+        """Test that a diff that no longer matches the table is rejected."""
+        loco_bindings.generate_model(str(project), "post", {"title": "string"}, True)
+
+        with pytest.raises(loco_bindings.ValidationError) as exc_info:
+            loco_bindings.generate_alter_migration(
+                str(project), "post", {"removed": [{"name": "body", "type": "TextNull"}]}
+            )
+
+        assert "column 'body'" in str(exc_info.value)
+
+
+APP_RS = """use std::path::Path;
+
+#[async_trait]
+impl Hooks for App {
+    async fn seed(_ctx: &AppContext, _base: &Path) -> Result<()> {
+        Ok(())
+    }
+}
+"""
+
+USERS_ENTITY = """#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "users")]
+pub struct Model {
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub name: String,
+    pub email: Option<String>,
+}
+"""
+
+USERS = [
+    {"id": 1, "name": "Ada", "email": "ada@example.com"},
+    {"id": 2, "name": "Grace", "email": None},
+]
+
+
+class TestGenerateSeed:
+    """Test generate_seed writing and registering seed files."""
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_writes_records(self, project):
+        """Test that the seed file holds the records and the hook loads it."""
+        (project / "src").mkdir()
+        (project / "src" / "app.rs").write_text(APP_RS)
+
+        result = loco_bindings.generate_seed(str(project), "user", USERS)
+
+        assert result["success"] is True
+        assert result["registered"] is True
+        seed_file = project / "src" / "fixtures" / "users.yaml"
+        assert result["created_files"] == [str(seed_file)]
+        assert yaml.safe_load(seed_file.read_text()) == USERS
+
+        app_rs = (project / "src" / "app.rs").read_text()
+        assert "async fn seed(ctx: &AppContext, base: &Path) -> Result<()> {" in app_rs
+        assert (
+            "loco_rs::db::seed::<crate::models::_entities::users::ActiveModel>"
+            '(&ctx.db, &base.join("users.yaml").display().to_string()).await?;'
+        ) in app_rs
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_registers_each_table_once(self, project):
+        """Test that seeds for several tables are all loaded by the hook."""
+        (project / "src").mkdir()
+        (project / "src" / "app.rs").write_text(APP_RS)
+
+        loco_bindings.generate_seed(str(project), "user", USERS)
+        loco_bindings.generate_seed(str(project), "post", [{"title": "Hello"}])
+
+        app_rs = (project / "src" / "app.rs").read_text()
+        assert app_rs.count('"users.yaml"') == 1
+        assert app_rs.index('"users.yaml"') < app_rs.index('"posts.yaml"') < app_rs.index("Ok(())")
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_without_seed_hook(self, project):
+        """Test that the file is still written when there is no hook to register it in."""
+        result = loco_bindings.generate_seed(str(project), "user", USERS)
+
+        assert result["registered"] is False
+        assert "Warning" in result["messages"]
+        assert (project / "src" / "fixtures" / "users.yaml").exists()
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_failed_registration_removes_file(self, project):
+        """Test that the seed file is removed again when it cannot be registered."""
+        (project / "src").mkdir()
+        (project / "src" / "app.rs").write_bytes(b"\xff\xfe not utf-8")
+
+        with pytest.raises(loco_bindings.FileOperationError):
+            loco_bindings.generate_seed(str(project), "user", USERS)
+
+        assert not (project / "src" / "fixtures").exists()
+        assert (project / "src" / "app.rs").read_bytes() == b"\xff\xfe not utf-8"
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_unknown_column(self, project):
+        """Test that record keys are checked against the model's entity."""
+        entities = project / "src" / "models" / "_entities"
+        entities.mkdir(parents=True)
+        (entities / "users.rs").write_text(USERS_ENTITY)
+
+        with pytest.raises(loco_bindings.ValidationError) as exc_info:
+            loco_bindings.generate_seed(
+                str(project), "user", [{"name": "Ada"}, {"name": "Grace", "age": 36}]
+            )
+
+        assert "Record 1" in str(exc_info.value)
+        assert "age" in str(exc_info.value)
+        assert not (project / "src" / "fixtures" / "users.yaml").exists()
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_existing_seed_file(self, project):
+        """Test that an existing seed file is not overwritten."""
+        loco_bindings.generate_seed(str(project), "user", USERS)
+
+        with pytest.raises(loco_bindings.FileOperationError):
+            loco_bindings.generate_seed(str(project), "user", [{"id": 3, "name": "Alan"}])
+
+        seed_file = project / "src" / "fixtures" / "users.yaml"
+        assert yaml.safe_load(seed_file.read_text()) == USERS
+
+
+class TestRenderModelPreview:
+    """Test render_model_preview rendering without a project."""
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_renders_model(self, tmp_path, monkeypatch):
+        """Test that the model holds the name and declared fields."""
+        monkeypatch.chdir(tmp_path)
+
+        result = loco_bindings.render_model_preview(
+            "blog_post", {"title": "string!", "views": "int", "author": "references"}
+        )
+
+        assert result["success"] is True
+        model = result["model"]
+        assert '"blog_posts"' in model
+        assert '("title", ColType::String),' in model
+        assert '("views", ColType::IntegerNull),' in model
+        assert '("author", "")' in model
+        migration_path = next(path for path in result["files"] if path.startswith("migration/src/m"))
+        assert migration_path.endswith("_blog_posts.rs")
+        assert result["files"][migration_path] == model
+        assert list(tmp_path.iterdir()) == []
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_app_name_and_timestamps(self):
+        """Test that the app name is used and timestamps can be left out."""
+        result = loco_bindings.render_model_preview(
+            "post", {"title": "string"}, with_timestamps=False, app_name="blog"
+        )
+
+        assert "create_table_without_timestamps" in result["model"]
+        model_test = next(
+            content for path, content in result["files"].items() if path.startswith("tests/models/")
+        )
+        assert "use blog::app::App;" in model_test
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_invalid_field_type(self):
+        """Test that unknown field types are rejected."""
+        with pytest.raises(loco_bindings.ValidationError):
+            loco_bindings.render_model_preview("post", {"title": "strin"})
+
+
+class TestListComponents:
+    """Test the component schema used by IDE integrations."""
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_model_schema(self):
+        """Test that the model lists its field types and constraints."""
+        model = loco_bindings.list_components()["model"]
+
+        assert "string" in model["fields"]["types"]
+        assert "unique" in model["fields"]["constraints"]
+        assert [param["name"] for param in model["parameters"]] == [
+            "project_path",
+            "name",
+            "fields",
+            "with_timestamps",
+            "dry_run",
+        ]
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_all_components(self):
+        """Test that every generator is described."""
+        components = loco_bindings.list_components()
+
+        assert set(components) == {"model", "scaffold", "controller", "migration"}
+        assert components["scaffold"]["kinds"] == ["api", "html", "htmx"]
+        assert components["migration"]["operations"] == ["add_column", "drop_column"]
+        assert components["migration"]["rust_types"]["i32"] == "int"
@@ -0,0 +1,99 @@
+"""
+Tests for the naming helpers (to_snake_case, pluralize, singularize).
+
+The generators route table names and view directory names through the same
+pluralization, so these are also checked against generated migrations.
+"""
+
+import pytest
+
+try:
+    import loco_bindings
+    HAS_BINDINGS = True
+except ImportError:
+    HAS_BINDINGS = False
+    print("Warning: loco_bindings not available, skipping naming tests")
+
+
+WORDS = [
+    ("category", "categories"),
+    ("person", "people"),
+    ("box", "boxes"),
+    ("bus", "buses"),
+]
+
+
+class TestNamingHelpers:
+    """Test the naming helpers exposed to Python."""
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_pluralize(self):
+        """Test regular and irregular plurals."""
+        for singular, plural in WORDS:
+            assert loco_bindings.pluralize(singular) == plural
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_singularize(self):
+        """Test that singularize reverses pluralize."""
+        for singular, plural in WORDS:
+            assert loco_bindings.singularize(plural) == singular
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_compound_names(self):
+        """Test that only the last word of a compound name is inflected."""
+        assert loco_bindings.pluralize("blog_category") == "blog_categories"
+        assert loco_bindings.singularize("sales_people") == "sales_person"
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_to_snake_case(self):
+        """Test conversion from common naming styles."""
+        assert loco_bindings.to_snake_case("BlogPost") == "blog_post"
+        assert loco_bindings.to_snake_case("blogPost") == "blog_post"
+        assert loco_bindings.to_snake_case("blog-post") == "blog_post"
+        assert loco_bindings.to_snake_case("Blog Post") == "blog_post"
+        assert loco_bindings.to_snake_case("HTTPServer") == "http_server"
+        assert loco_bindings.to_snake_case("blog_post") == "blog_post"
+
+
+class TestGeneratedNames:
+    """Test that generated table names use the same pluralization."""
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_migration_table_names(self, project):
+        """Test that each model gets a correctly pluralized table."""
+        for singular, plural in WORDS:
+            loco_bindings.generate_model(str(project), singular, {"name": "string"}, True)
+
+            migration = next((project / "migration" / "src").glob(f"m*_{plural}.rs"))
+            assert f'create_table(m, "{plural}",' in migration.read_text()
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_foreign_key_to_irregular_table(self, project):
+        """Test that foreign keys singularize irregular table names."""
+        loco_bindings.generate_model(
+            str(project), "post", {"author_id": "int:foreign_key:people"}, True
+        )
+
+        migration = next((project / "migration" / "src").glob("m*_posts.rs"))
+        assert '("person", "author_id")' in migration.read_text()
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_project_inflections(self, project):
+        """Test that every helper taking a project honors its inflections."""
+        (project / ".loco-inflections.yaml").write_text("irregular:\n  person: persons\n")
+
+        loco_bindings.generate_model(str(project), "person", {"name": "string"}, True)
+        loco_bindings.generate_model(
+            str(project), "post", {"author_id": "int:foreign_key:persons"}, True
+        )
+
+        post = next((project / "migration" / "src").glob("m*_posts.rs")).read_text()
+        assert '("person", "author_id")' in post
+        diff = loco_bindings.diff_model(str(project), "person", {"name": "string"})
+        assert diff["table"] == "persons"
+        result = loco_bindings.validate_generation(str(project), "person", {"name": "string"})
+        assert "already exists" in result["errors"][0]["message"]
+
+
+if __name__ == "__main__":
+    pytest.main([__file__, "-v"])
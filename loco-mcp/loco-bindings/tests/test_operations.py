@@ -0,0 +1,262 @@
+"""
+Tests for the privileged operation bindings (migrate_db, rotate_keys, clean_temp).
+
+`cargo` is replaced by a stub script on PATH, so no real toolchain or
+database is needed. Calls are checked against a `loco.operations.toml`
+written into the test project.
+"""
+
+import os
+import stat
+import subprocess
+import sys
+import time
+
+import pytest
+
+try:
+    import loco_bindings
+    HAS_BINDINGS = True
+except ImportError:
+    HAS_BINDINGS = False
+    print("Warning: loco_bindings not available, skipping operation tests")
+
+
+POLICY = """
+[migrate_db]
+approvals = ["ops_lead", "security_officer"]
+dependencies = ["postgres", "redis"]
+
+[rotate_keys]
+approvals = ["security_officer", "cto"]
+dependencies = ["kms"]
+
+[clean_temp]
+approvals = ["ops_lead"]
+dependencies = ["fs-local"]
+"""
+
+MIGRATE_ARGS = {
+    "approvals": ["ops_lead", "security_officer"],
+    "dependencies": ["postgres", "redis"],
+}
+
+
+def write_policy(project, policy=POLICY):
+    """Write the project's operation policy."""
+    (project / "loco.operations.toml").write_text(policy)
+
+
+def install_stub_cargo(directory, monkeypatch, script):
+    """Put a `cargo` shell script first on PATH."""
+    bin_dir = directory / ".stub-bin"
+    bin_dir.mkdir()
+    cargo = bin_dir / "cargo"
+    cargo.write_text("#!/bin/sh\n" + script)
+    cargo.chmod(cargo.stat().st_mode | stat.S_IXUSR)
+    monkeypatch.setenv("PATH", f"{bin_dir}{os.pathsep}{os.environ['PATH']}")
+
+
+class TestMigrateDb:
+    """Test that migrate_db runs the project's migration runner."""
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_runs_migrations(self, project, monkeypatch):
+        """Test that the runner output and applied migrations are reported."""
+        (project / "migration" / "src" / "m20240101_000001_users.rs").write_text("")
+        write_policy(project)
+        install_stub_cargo(
+            project,
+            monkeypatch,
+            'echo "args: $*"\necho "env: $LOCO_ENV"\necho "Applying migration m20240101_000001_users" >&2\n',
+        )
+
+        result = loco_bindings.migrate_db(str(project), environment="test", **MIGRATE_ARGS)
+
+        assert result["success"] is True
+        assert "args: loco db migrate" in result["messages"]
+        assert "env: test" in result["messages"]
+        assert "Applying migration m20240101_000001_users" in result["messages"]
+        assert result["migrations"] == ["m20240101_000001_users"]
+        assert result["checksum"].startswith("migrate_")
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_checksum_follows_migrations(self, project, monkeypatch):
+        """Test that the checksum changes when another migration is applied."""
+        write_policy(project)
+        install_stub_cargo(project, monkeypatch, "exit 0\n")
+        (project / "migration" / "src" / "m20240101_000001_users.rs").write_text("")
+
+        first = loco_bindings.migrate_db(str(project), **MIGRATE_ARGS)["checksum"]
+        assert loco_bindings.migrate_db(str(project), **MIGRATE_ARGS)["checksum"] == first
+
+        (project / "migration" / "src" / "m20240102_000001_posts.rs").write_text("")
+        assert loco_bindings.migrate_db(str(project), **MIGRATE_ARGS)["checksum"] != first
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_runner_failure(self, project, monkeypatch):
+        """Test that a failing runner raises with its output."""
+        write_policy(project)
+        install_stub_cargo(project, monkeypatch, 'echo "connection refused" >&2\nexit 1\n')
+
+        with pytest.raises(loco_bindings.ProjectError) as exc_info:
+            loco_bindings.migrate_db(str(project), **MIGRATE_ARGS)
+
+        assert "connection refused" in str(exc_info.value)
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_timeout(self, project, monkeypatch):
+        """Test that a runner exceeding the timeout is stopped with an error."""
+        write_policy(
+            project,
+            '[migrate_db]\napprovals = []\ntimeout_seconds = { min = 1, max = 300 }\n',
+        )
+        install_stub_cargo(project, monkeypatch, "sleep 10\n")
+
+        started = time.monotonic()
+        with pytest.raises(loco_bindings.ProjectError) as exc_info:
+            loco_bindings.migrate_db(str(project), [], [], timeout_seconds=1)
+
+        assert "timed out after 1 seconds" in str(exc_info.value)
+        assert time.monotonic() - started < 5
+
+
+class TestOperationPolicy:
+    """Test that calls are validated against loco.operations.toml."""
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_missing_policy(self, project):
+        """Test that running without a policy file is a project error."""
+        with pytest.raises(loco_bindings.ProjectError) as exc_info:
+            loco_bindings.clean_temp(str(project), ["ops_lead"], ["fs-local"])
+
+        assert "loco.operations.toml" in str(exc_info.value)
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_missing_operation(self, project):
+        """Test that an operation without a policy table is a project error."""
+        write_policy(project, '[clean_temp]\napprovals = ["ops_lead"]\n')
+
+        with pytest.raises(loco_bindings.ProjectError) as exc_info:
+            loco_bindings.rotate_keys(str(project), ["security_officer", "cto"], ["kms"])
+
+        assert "[rotate_keys]" in str(exc_info.value)
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_custom_approval_order(self, project):
+        """Test that the approver order comes from the policy file."""
+        write_policy(
+            project,
+            '[rotate_keys]\napprovals = ["cto", "security_officer"]\ndependencies = ["kms"]\n',
+        )
+
+        with pytest.raises(loco_bindings.ValidationError) as exc_info:
+            loco_bindings.rotate_keys(str(project), ["security_officer", "cto"], ["kms"])
+        assert "approvals must follow required order" in str(exc_info.value)
+
+        result = loco_bindings.rotate_keys(str(project), ["cto", "security_officer"], ["kms"])
+        assert result["success"] is True
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_timeout_range(self, project):
+        """Test that timeouts outside the policy range are rejected."""
+        write_policy(
+            project,
+            '[clean_temp]\napprovals = ["ops_lead"]\ntimeout_seconds = { min = 5, max = 30 }\n',
+        )
+
+        result = loco_bindings.clean_temp(str(project), ["ops_lead"], [], timeout_seconds=5)
+        assert result["success"] is True
+
+        with pytest.raises(loco_bindings.ValidationError) as exc_info:
+            loco_bindings.clean_temp(str(project), ["ops_lead"], [], timeout_seconds=60)
+        assert "between 5 and 30" in str(exc_info.value)
+
+
+class TestIdempotency:
+    """Test that a repeated idempotency key replays the recorded result."""
+
+    @staticmethod
+    def _install_counting_cargo(project, monkeypatch):
+        runs = project / "runs.log"
+        install_stub_cargo(project, monkeypatch, f'echo run >> "{runs}"\necho "migrated"\n')
+        return runs
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_same_key_runs_once(self, project, monkeypatch):
+        """Test that the second call with a key does not run the migration."""
+        write_policy(project)
+        runs = self._install_counting_cargo(project, monkeypatch)
+
+        first = loco_bindings.migrate_db(str(project), idempotency_key="deploy-42", **MIGRATE_ARGS)
+        second = loco_bindings.migrate_db(str(project), idempotency_key="deploy-42", **MIGRATE_ARGS)
+
+        assert runs.read_text().splitlines() == ["run"]
+        assert first["replayed"] is False
+        assert second["replayed"] is True
+        assert second["messages"] == first["messages"]
+        assert second["checksum"] == first["checksum"]
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_concurrent_calls_run_once(self, project, monkeypatch):
+        """Test that calls with one key from parallel processes run the migration once."""
+        write_policy(project)
+        runs = project / "runs.log"
+        install_stub_cargo(project, monkeypatch, f'echo run >> "{runs}"\nsleep 0.5\necho "migrated"\n')
+        env = {**os.environ, "PYTHONPATH": os.pathsep.join(sys.path)}
+        processes = [
+            subprocess.Popen(
+                [
+                    sys.executable,
+                    "-c",
+                    "import sys, loco_bindings; "
+                    "loco_bindings.migrate_db(sys.argv[1], idempotency_key='deploy-42', "
+                    "approvals=['ops_lead', 'security_officer'], dependencies=['postgres', 'redis'])",
+                    str(project),
+                ],
+                env=env,
+                stdout=subprocess.DEVNULL,
+            )
+            for _ in range(4)
+        ]
+        assert all(process.wait(timeout=60) == 0 for process in processes)
+
+        assert runs.read_text().splitlines() == ["run"]
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_different_keys_run_again(self, project, monkeypatch):
+        """Test that a new key or no key runs the operation."""
+        write_policy(project)
+        runs = self._install_counting_cargo(project, monkeypatch)
+
+        loco_bindings.migrate_db(str(project), idempotency_key="deploy-42", **MIGRATE_ARGS)
+        loco_bindings.migrate_db(str(project), idempotency_key="deploy-43", **MIGRATE_ARGS)
+        loco_bindings.migrate_db(str(project), **MIGRATE_ARGS)
+
+        assert runs.read_text().splitlines() == ["run", "run", "run"]
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_expired_key_runs_again(self, project, monkeypatch):
+        """Test that a key older than the policy window is not replayed."""
+        write_policy(project, POLICY.replace(
+            'dependencies = ["postgres", "redis"]',
+            'dependencies = ["postgres", "redis"]\nidempotency_window_seconds = 0',
+        ))
+        runs = self._install_counting_cargo(project, monkeypatch)
+
+        loco_bindings.migrate_db(str(project), idempotency_key="deploy-42", **MIGRATE_ARGS)
+        result = loco_bindings.migrate_db(str(project), idempotency_key="deploy-42", **MIGRATE_ARGS)
+
+        assert runs.read_text().splitlines() == ["run", "run"]
+        assert result["replayed"] is False
+
+    @pytest.mark.skipif(not HAS_BINDINGS, reason="loco_bindings not available")
+    def test_failed_call_is_not_recorded(self, project, monkeypatch):
+        """Test that a failed run can be retried with the same key."""
+        write_policy(project)
+        install_stub_cargo(project, monkeypatch, "exit 1\n")
+
+        with pytest.raises(loco_bindings.ProjectError):
+            loco_bindings.migrate_db(str(project), idempotency_key="deploy-42", **MIGRATE_ARGS)
+
+        assert not (project / ".loco" / "idempotency.json").exists()
@@ -29,11 +29,18 @@ use crate::{
     Result,
 };
 
+/// Storage key: the value's type, and its name for keyed values
+type StoreKey = (TypeId, Option<String>);
+
 /// Type-safe heterogeneous storage for arbitrary application data
+///
+/// Values are stored once per type, or once per type and name with the
+/// `*_keyed` methods, which allows storing several values of the same type
+/// (e.g. multiple HTTP clients) side by side.
 #[derive(Default, Debug)]
 pub struct SharedStore {
     // Use DashMap for concurrent access with fine-grained locking
-    storage: DashMap<TypeId, Box<dyn Any + Send + Sync>>,
+    storage: DashMap<StoreKey, Box<dyn Any + Send + Sync>>,
 }
 
 impl SharedStore {
@@ -59,7 +66,8 @@ impl SharedStore {
     /// assert!(shared_store.contains::<TestService>());
     /// ```
     pub fn insert<T: 'static + Send + Sync>(&self, val: T) {
-        self.storage.insert(TypeId::of::<T>(), Box::new(val));
+        self.storage
+            .insert((TypeId::of::<T>(), None), Box::new(val));
     }
 
     /// Remove a value of type T from the shared store
@@ -101,7 +109,7 @@ impl SharedStore {
     #[must_use]
     pub fn remove<T: 'static + Send + Sync>(&self) -> Option<T> {
         self.storage
-            .remove(&TypeId::of::<T>())
+            .remove(&(TypeId::of::<T>(), None))
             .map(|(_, v)| v) // Extract the Box<dyn Any>
             .and_then(|any| any.downcast::<T>().ok()) // Downcast to Box<T>
             .map(|boxed| *boxed) // Dereference the Box<T> to get T
@@ -148,11 +156,47 @@ impl SharedStore {
     /// ```
     #[must_use]
     pub fn get_ref<T: 'static + Send + Sync>(&self) -> Option<RefGuard<'_, T>> {
-        let type_id = TypeId::of::<T>();
-        self.storage.get(&type_id).map(|r| RefGuard::<T> {
-            inner: r,
-            _phantom: std::marker::PhantomData,
-        })
+        self.storage
+            .get(&(TypeId::of::<T>(), None))
+            .map(|r| RefGuard::<T> {
+                inner: r,
+                _phantom: std::marker::PhantomData,
+            })
+    }
+
+    /// Get a mutable reference to a value of type T from the shared store.
+    ///
+    /// Returns `None` if the value doesn't exist.
+    /// While the returned `RefMutGuard` is held, any other access to the
+    /// same entry (and possibly to other entries sharing its shard) blocks,
+    /// so keep it short-lived and never hold it across an `.await`.
+    ///
+    /// # Example
+    /// ```
+    /// # use loco_rs::app::SharedStore;
+    /// let shared_store = SharedStore::default();
+    ///
+    /// struct Counter {
+    ///     count: u32,
+    /// }
+    ///
+    /// shared_store.insert(Counter { count: 0 });
+    ///
+    /// if let Some(mut counter) = shared_store.get_mut::<Counter>() {
+    ///     counter.count += 1;
+    /// }
+    ///
+    /// let counter = shared_store.get_ref::<Counter>().expect("Counter not found");
+    /// assert_eq!(counter.count, 1);
+    /// ```
+    #[must_use]
+    pub fn get_mut<T: 'static + Send + Sync>(&self) -> Option<RefMutGuard<'_, T>> {
+        self.storage
+            .get_mut(&(TypeId::of::<T>(), None))
+            .map(|r| RefMutGuard::<T> {
+                inner: r,
+                _phantom: std::marker::PhantomData,
+            })
     }
 
     /// Get a clone of a value of type T from the shared store.
@@ -195,6 +239,50 @@ impl SharedStore {
         self.get_ref::<T>().map(|guard| (*guard).clone())
     }
 
+    /// Get a reference to a value of type T, inserting the result of `f` if
+    /// it doesn't exist yet.
+    ///
+    /// The lookup and the insert happen under the same lock, so `f` runs at
+    /// most once even when called concurrently. `f` must not access the
+    /// shared store itself, as that would deadlock.
+    ///
+    /// # Example
+    /// ```
+    /// # use loco_rs::app::SharedStore;
+    /// let shared_store = SharedStore::default();
+    ///
+    /// struct TestService {
+    ///     name: String,
+    /// }
+    ///
+    /// let service = shared_store.get_or_insert_with(|| TestService {
+    ///     name: "first".to_string(),
+    /// });
+    /// assert_eq!(service.name, "first");
+    /// drop(service);
+    ///
+    /// // The existing value is returned, `f` is not called
+    /// let service = shared_store.get_or_insert_with(|| TestService {
+    ///     name: "second".to_string(),
+    /// });
+    /// assert_eq!(service.name, "first");
+    /// ```
+    #[must_use]
+    pub fn get_or_insert_with<T: 'static + Send + Sync, F: FnOnce() -> T>(
+        &self,
+        f: F,
+    ) -> RefGuard<'_, T> {
+        let inner = self
+            .storage
+            .entry((TypeId::of::<T>(), None))
+            .or_insert_with(|| Box::new(f()))
+            .downgrade();
+        RefGuard::<T> {
+            inner,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
     /// Check if the shared store contains a value of type T
     ///
     /// # Example
@@ -218,14 +306,87 @@ impl SharedStore {
     /// ```
     #[must_use]
     pub fn contains<T: 'static + Send + Sync>(&self) -> bool {
-        self.storage.contains_key(&TypeId::of::<T>())
+        self.storage.contains_key(&(TypeId::of::<T>(), None))
+    }
+
+    /// Insert a value of type T under the given name
+    ///
+    /// Keyed values live next to the type-keyed ones: a value inserted with
+    /// `insert_keyed` is not returned by `get`, and vice versa.
+    ///
+    /// # Example
+    /// ```
+    /// # use loco_rs::app::SharedStore;
+    /// let shared_store = SharedStore::default();
+    ///
+    /// shared_store.insert_keyed("primary", "https://primary.example.com".to_string());
+    /// shared_store.insert_keyed("fallback", "https://fallback.example.com".to_string());
+    ///
+    /// assert!(shared_store.contains_keyed::<String>("primary"));
+    /// assert!(!shared_store.contains::<String>());
+    /// ```
+    pub fn insert_keyed<T: 'static + Send + Sync>(&self, key: impl Into<String>, val: T) {
+        self.storage
+            .insert((TypeId::of::<T>(), Some(key.into())), Box::new(val));
+    }
+
+    /// Remove the value of type T stored under the given name
+    ///
+    /// Returns `Some(T)` if the value was present and removed, `None` otherwise.
+    #[must_use]
+    pub fn remove_keyed<T: 'static + Send + Sync>(&self, key: &str) -> Option<T> {
+        self.storage
+            .remove(&(TypeId::of::<T>(), Some(key.to_string())))
+            .and_then(|(_, any)| any.downcast::<T>().ok())
+            .map(|boxed| *boxed)
+    }
+
+    /// Get a reference to the value of type T stored under the given name
+    ///
+    /// Returns `None` if the value doesn't exist.
+    ///
+    /// # Example
+    /// ```
+    /// # use loco_rs::app::SharedStore;
+    /// let shared_store = SharedStore::default();
+    ///
+    /// shared_store.insert_keyed("primary", "https://primary.example.com".to_string());
+    ///
+    /// let url = shared_store.get_ref_keyed::<String>("primary").expect("URL not found");
+    /// assert_eq!(url.as_str(), "https://primary.example.com");
+    /// assert!(shared_store.get_ref_keyed::<String>("fallback").is_none());
+    /// ```
+    #[must_use]
+    pub fn get_ref_keyed<T: 'static + Send + Sync>(&self, key: &str) -> Option<RefGuard<'_, T>> {
+        self.storage
+            .get(&(TypeId::of::<T>(), Some(key.to_string())))
+            .map(|r| RefGuard::<T> {
+                inner: r,
+                _phantom: std::marker::PhantomData,
+            })
+    }
+
+    /// Get a clone of the value of type T stored under the given name
+    ///
+    /// Returns `None` if the value doesn't exist.
+    #[must_use]
+    pub fn get_keyed<T: 'static + Send + Sync + Clone>(&self, key: &str) -> Option<T> {
+        self.get_ref_keyed::<T>(key).map(|guard| (*guard).clone())
+    }
+
+    /// Check if the shared store contains a value of type T under the given
+    /// name
+    #[must_use]
+    pub fn contains_keyed<T: 'static + Send + Sync>(&self, key: &str) -> bool {
+        self.storage
+            .contains_key(&(TypeId::of::<T>(), Some(key.to_string())))
     }
 }
 
 // A wrapper around DashMap's Ref type that erases the exact type
 // but provides deref to the target type
 pub struct RefGuard<'a, T: 'static + Send + Sync> {
-    inner: dashmap::mapref::one::Ref<'a, TypeId, Box<dyn Any + Send + Sync>>,
+    inner: dashmap::mapref::one::Ref<'a, StoreKey, Box<dyn Any + Send + Sync>>,
     _phantom: std::marker::PhantomData<&'a T>,
 }
 
@@ -243,6 +404,37 @@ impl<T: 'static + Send + Sync> std::ops::Deref for RefGuard<'_, T> {
     }
 }
 
+// A wrapper around DashMap's RefMut type that erases the exact type
+// but provides mutable deref to the target type
+pub struct RefMutGuard<'a, T: 'static + Send + Sync> {
+    inner: dashmap::mapref::one::RefMut<'a, StoreKey, Box<dyn Any + Send + Sync>>,
+    _phantom: std::marker::PhantomData<&'a mut T>,
+}
+
+impl<T: 'static + Send + Sync> std::ops::Deref for RefMutGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // This is safe because we only create a RefMutGuard for a specific type
+        // after looking it up by its TypeId
+        #[allow(clippy::coerce_container_to_any)]
+        self.inner
+            .value()
+            .downcast_ref::<T>()
+            .expect("Type mismatch in RefMutGuard")
+    }
+}
+
+impl<T: 'static + Send + Sync> std::ops::DerefMut for RefMutGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        #[allow(clippy::coerce_container_to_any)]
+        self.inner
+            .value_mut()
+            .downcast_mut::<T>()
+            .expect("Type mismatch in RefMutGuard")
+    }
+}
+
 /// Represents the application context for a web server.
 ///
 /// This struct encapsulates various components and configurations required by
@@ -637,6 +829,96 @@ mod tests {
         // let non_existent_clone = shared_store.get::<TestService>();
     }
 
+    #[test]
+    fn test_extensions_get_mut() {
+        let shared_store = SharedStore::default();
+
+        shared_store.insert(TestService {
+            name: "counter".to_string(),
+            value: 0,
+        });
+
+        for _ in 0..3 {
+            let mut service = shared_store
+                .get_mut::<TestService>()
+                .expect("Service should exist");
+            service.value += 1;
+        }
+
+        let service_ref = shared_store
+            .get_ref::<TestService>()
+            .expect("Service should exist");
+        assert_eq!(service_ref.name, "counter");
+        assert_eq!(service_ref.value, 3);
+        assert!(shared_store.get_mut::<String>().is_none());
+    }
+
+    #[test]
+    fn test_extensions_keyed() {
+        let shared_store = SharedStore::default();
+
+        shared_store.insert_keyed("primary", "https://primary.example.com".to_string());
+        shared_store.insert_keyed("fallback", "https://fallback.example.com".to_string());
+        shared_store.insert("unkeyed".to_string());
+
+        assert_eq!(
+            shared_store.get_keyed::<String>("primary").as_deref(),
+            Some("https://primary.example.com")
+        );
+        assert_eq!(
+            shared_store
+                .get_ref_keyed::<String>("fallback")
+                .map(|url| url.len()),
+            Some("https://fallback.example.com".len())
+        );
+        assert_eq!(shared_store.get::<String>().as_deref(), Some("unkeyed"));
+        assert!(!shared_store.contains_keyed::<String>("missing"));
+        assert!(!shared_store.contains_keyed::<i32>("primary"));
+
+        assert_eq!(
+            shared_store.remove_keyed::<String>("primary").as_deref(),
+            Some("https://primary.example.com")
+        );
+        assert!(!shared_store.contains_keyed::<String>("primary"));
+        assert!(shared_store.contains_keyed::<String>("fallback"));
+        assert!(shared_store.contains::<String>());
+    }
+
+    #[test]
+    fn test_extensions_get_or_insert_with() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Barrier,
+        };
+
+        let shared_store = SharedStore::default();
+        let calls = AtomicUsize::new(0);
+        let barrier = Barrier::new(16);
+
+        std::thread::scope(|s| {
+            for i in 0..16 {
+                let (shared_store, calls, barrier) = (&shared_store, &calls, &barrier);
+                s.spawn(move || {
+                    barrier.wait();
+                    let service = shared_store.get_or_insert_with(|| {
+                        calls.fetch_add(1, Ordering::SeqCst);
+                        TestService {
+                            name: format!("service_{i}"),
+                            value: i,
+                        }
+                    });
+                    assert!(service.name.starts_with("service_"));
+                });
+            }
+        });
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        let service = shared_store
+            .get_ref::<TestService>()
+            .expect("Service should exist");
+        assert_eq!(service.name, format!("service_{}", service.value));
+    }
+
     #[tokio::test]
     async fn test_app_context_extensions() {
         let ctx = get_app_context().await;
@@ -343,6 +343,10 @@ After running the migration, follow these steps to complete the process:
         /// Use API scaffold
         #[clap(long, group = "scaffold_kind_group")]
         api: bool,
+
+        /// Wrap each controller action in a tracing span
+        #[arg(long, action)]
+        tracing: bool,
     },
     /// Generate a new controller with the given controller name, and test file.
     #[command(after_help = format!(
@@ -465,6 +469,7 @@ impl ComponentArg {
                 htmx,
                 html,
                 api,
+                tracing,
             } => {
                 let kind = if let Some(kind) = kind {
                     kind
@@ -485,6 +490,7 @@ impl ComponentArg {
                     with_tz: !without_tz,
                     fields,
                     kind,
+                    with_tracing: tracing,
                 })
             }
             Self::Controller {
@@ -1375,12 +1381,17 @@ fn handle_generate_command<H: Hooks>(
             }
         }
     } else {
-        let get_result = loco_gen::generate(
+        let options = loco_gen::GenerateOptions {
+            inflections: loco_gen::inflection::Inflections::load_local()?,
+            ..Default::default()
+        };
+        let get_result = loco_gen::generate_with_options(
             &loco_gen::new_generator(),
             component.into_gen_component(config)?,
             &loco_gen::AppInfo {
                 app_name: H::app_name().to_string(),
             },
+            &options,
         )?;
         let messages = loco_gen::collect_messages(&get_result);
         println!("{messages}");
@@ -0,0 +1,132 @@
+//! Serde helpers to (de)serialize binary fields as base64 strings instead of
+//! arrays of numbers.
+//!
+//! # Example:
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! pub struct Params {
+//!     #[serde(with = "loco_rs::controller::base64")]
+//!     pub thumbnail: Vec<u8>,
+//!     #[serde(default, with = "loco_rs::controller::base64::option")]
+//!     pub attachment: Option<Vec<u8>>,
+//! }
+//! ```
+use ::base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{de, Deserialize, Deserializer, Serializer};
+
+/// Encodes bytes as a standard base64 string.
+#[must_use]
+pub fn encode(bytes: &[u8]) -> String {
+    STANDARD.encode(bytes)
+}
+
+/// Decodes a standard base64 string.
+///
+/// # Errors
+///
+/// When the input is not valid base64.
+pub fn decode(input: &str) -> Result<Vec<u8>, ::base64::DecodeError> {
+    STANDARD.decode(input)
+}
+
+/// Serializes bytes as a base64 string.
+///
+/// # Errors
+///
+/// When the serializer fails.
+pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&encode(bytes))
+}
+
+/// Deserializes bytes from a base64 string.
+///
+/// # Errors
+///
+/// When the value is not a valid base64 string.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    decode(&value).map_err(de::Error::custom)
+}
+
+/// Same as the parent module, for optional binary fields.
+pub mod option {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    /// Serializes optional bytes as a base64 string or `null`.
+    ///
+    /// # Errors
+    ///
+    /// When the serializer fails.
+    pub fn serialize<S: Serializer>(
+        bytes: &Option<Vec<u8>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match bytes {
+            Some(bytes) => super::serialize(bytes, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserializes optional bytes from a base64 string or `null`.
+    ///
+    /// # Errors
+    ///
+    /// When the value is not a valid base64 string.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Vec<u8>>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|value| super::decode(&value).map_err(de::Error::custom))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Params {
+        #[serde(with = "super")]
+        thumbnail: Vec<u8>,
+        #[serde(default, with = "super::option")]
+        attachment: Option<Vec<u8>>,
+    }
+
+    #[test]
+    fn can_round_trip() {
+        let params = Params {
+            thumbnail: vec![0, 1, 2, 254, 255],
+            attachment: Some(b"loco".to_vec()),
+        };
+
+        let value = serde_json::to_value(&params).unwrap();
+        assert_eq!(
+            value,
+            json!({"thumbnail": "AAEC/v8=", "attachment": "bG9jbw=="})
+        );
+        assert_eq!(serde_json::from_value::<Params>(value).unwrap(), params);
+    }
+
+    #[test]
+    fn can_deserialize_missing_optional_field() {
+        let params: Params = serde_json::from_value(json!({"thumbnail": ""})).unwrap();
+        assert_eq!(
+            params,
+            Params {
+                thumbnail: vec![],
+                attachment: None,
+            }
+        );
+    }
+
+    #[test]
+    fn fails_on_invalid_base64() {
+        let res = serde_json::from_value::<Params>(json!({"thumbnail": "not base64!"}));
+        assert!(res.is_err());
+    }
+}
@@ -79,12 +79,14 @@ use crate::{errors::Error, Result};
 
 mod app_routes;
 mod backtrace;
+pub mod base64;
 mod describe;
 pub mod extractor;
 pub mod format;
 pub mod middleware;
 pub mod monitoring;
 mod routes;
+pub mod upload;
 pub mod views;
 
 /// Create an unauthorized error with a specified message.
@@ -0,0 +1,195 @@
+//! Read multipart forms whose file fields are stored with the app's storage.
+//!
+//! # Example:
+//!
+//! ```rust
+//! use loco_rs::{controller::upload::UploadForm, prelude::*};
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! pub struct Params {
+//!     pub name: String,
+//!     pub age: Option<i32>,
+//!     /// Storage key of the uploaded avatar
+//!     pub avatar: Option<String>,
+//! }
+//!
+//! pub async fn add(State(ctx): State<AppContext>, multipart: Multipart) -> Result<Response> {
+//!     let params: Params = UploadForm::new("profiles")
+//!         .file("avatar")
+//!         .parse(&ctx, multipart)
+//!         .await?;
+//!     format::json(params.avatar)
+//! }
+//! ```
+use std::path::{Path, PathBuf};
+
+use axum::extract::{multipart::Field, Multipart};
+use bytes::Bytes;
+use serde::de::DeserializeOwned;
+
+use crate::{app::AppContext, errors::Error, Result};
+
+/// Maximum size, in bytes, accepted for an uploaded file by default.
+pub const DEFAULT_MAX_SIZE: usize = 10 * 1024 * 1024;
+
+/// Content types accepted for uploaded files by default.
+pub const DEFAULT_CONTENT_TYPES: &[&str] = &[
+    "image/png",
+    "image/jpeg",
+    "image/gif",
+    "image/webp",
+    "application/pdf",
+];
+
+/// A multipart form with file fields.
+///
+/// Files are checked against the allowed content types and size, then
+/// stored under `dir` with a unique key, which replaces the file as the
+/// field's value. The other fields are read as text and deserialized the
+/// way a submitted form is, so numbers and booleans parse from their text.
+/// A file field submitted without a file is left out.
+///
+/// The request body is still capped by the `limit_payload` middleware, so it
+/// must allow at least the largest accepted file.
+#[derive(Debug, Clone)]
+pub struct UploadForm {
+    dir: PathBuf,
+    files: Vec<String>,
+    max_size: usize,
+    content_types: Vec<String>,
+}
+
+impl UploadForm {
+    /// Creates a form storing its files under `dir`.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            files: Vec::new(),
+            max_size: DEFAULT_MAX_SIZE,
+            content_types: DEFAULT_CONTENT_TYPES
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+
+    /// Declares `name` as a file field.
+    #[must_use]
+    pub fn file(mut self, name: impl Into<String>) -> Self {
+        self.files.push(name.into());
+        self
+    }
+
+    /// Sets the maximum size, in bytes, of each uploaded file.
+    #[must_use]
+    pub const fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Replaces the accepted content types.
+    #[must_use]
+    pub fn content_types(mut self, content_types: &[&str]) -> Self {
+        self.content_types = content_types.iter().map(ToString::to_string).collect();
+        self
+    }
+
+    /// Reads the form into `T`, storing its files.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BadRequest`] when the form cannot be read, a file is
+    /// too large or of a content type that is not allowed, or the fields do
+    /// not deserialize into `T`, and any error from storing a file.
+    pub async fn parse<T: DeserializeOwned>(
+        &self,
+        ctx: &AppContext,
+        mut multipart: Multipart,
+    ) -> Result<T> {
+        let mut values = Vec::new();
+        while let Some(field) = multipart.next_field().await.map_err(|err| {
+            tracing::error!(error = ?err, "could not read multipart");
+            Error::BadRequest("could not read multipart".into())
+        })? {
+            let Some(name) = field.name().map(ToString::to_string) else {
+                continue;
+            };
+            let value = if self.files.contains(&name) {
+                match self.store(ctx, field).await? {
+                    Some(key) => key,
+                    None => continue,
+                }
+            } else {
+                field.text().await.map_err(|err| {
+                    tracing::error!(error = ?err, "could not read multipart field");
+                    Error::BadRequest("could not read multipart field".into())
+                })?
+            };
+            values.push((name, value));
+        }
+
+        let encoded = serde_urlencoded::to_string(&values)
+            .map_err(|err| Error::BadRequest(err.to_string()))?;
+        serde_urlencoded::from_str(&encoded).map_err(|err| Error::BadRequest(err.to_string()))
+    }
+
+    /// Checks and stores the file of `field`, returning its storage key, or
+    /// `None` when the field was submitted without a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BadRequest`] when the file is too large, of a content
+    /// type that is not allowed, or cannot be read, and any error from
+    /// storing it.
+    pub async fn store(&self, ctx: &AppContext, field: Field<'_>) -> Result<Option<String>> {
+        let file_name = match field.file_name() {
+            Some(file_name) if !file_name.is_empty() => file_name.to_string(),
+            _ => return Ok(None),
+        };
+
+        let content_type = field.content_type().unwrap_or_default();
+        if !self
+            .content_types
+            .iter()
+            .any(|allowed| allowed == content_type)
+        {
+            return Err(Error::BadRequest(format!(
+                "content type `{content_type}` is not allowed"
+            )));
+        }
+
+        let content = self.read_file(field).await?;
+
+        let file_name = Path::new(&file_name)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("upload");
+        let key = self
+            .dir
+            .join(format!("{}-{file_name}", uuid::Uuid::new_v4()));
+        ctx.storage.as_ref().upload(&key, &content).await?;
+
+        Ok(Some(key.display().to_string()))
+    }
+
+    /// Reads a file chunk by chunk, failing as soon as it exceeds the
+    /// maximum size instead of buffering the rest of it.
+    async fn read_file(&self, mut field: Field<'_>) -> Result<Bytes> {
+        let mut content = Vec::new();
+        while let Some(chunk) = field.chunk().await.map_err(|err| {
+            tracing::error!(error = ?err, "could not read uploaded file");
+            Error::BadRequest("could not read uploaded file".into())
+        })? {
+            if content.len() + chunk.len() > self.max_size {
+                return Err(Error::BadRequest(format!(
+                    "file exceeds the maximum size of {} bytes",
+                    self.max_size
+                )));
+            }
+            content.extend_from_slice(&chunk);
+        }
+        Ok(content.into())
+    }
+}
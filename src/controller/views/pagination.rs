@@ -1,5 +1,11 @@
+use axum::response::{IntoResponse, Response};
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    controller::Json,
+    model::query::{PageResponse, PaginationQuery},
+};
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Pager<T> {
     #[serde(rename(serialize = "results"))]
@@ -30,3 +36,152 @@ impl<T> Pager<T> {
         }
     }
 }
+
+/// A paginated JSON envelope shared by controllers returning a page of
+/// items.
+///
+/// ```json
+/// {
+///   "data": [...],
+///   "page": 2,
+///   "per_page": 25,
+///   "total": 60,
+///   "links": { "first": "...", "prev": "...", "next": "...", "last": "..." }
+/// }
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PaginatedResponse<T> {
+    pub data: Vec<T>,
+    pub page: u64,
+    pub per_page: u64,
+    pub total: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub links: Option<PaginationLinks>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PaginationLinks {
+    pub first: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+    pub last: String,
+}
+
+impl<T> PaginatedResponse<T> {
+    #[must_use]
+    pub const fn new(data: Vec<T>, page: u64, per_page: u64, total: u64) -> Self {
+        Self {
+            data,
+            page,
+            per_page,
+            total,
+            links: None,
+        }
+    }
+
+    /// Builds a response from the result of [`crate::model::query::paginate`]
+    /// and the query it was fetched with.
+    #[must_use]
+    pub fn from_page(res: PageResponse<T>, query: &PaginationQuery) -> Self {
+        Self::new(res.page, query.page, query.page_size, res.total_items)
+    }
+
+    /// Converts every item of the page, keeping the pagination info.
+    #[must_use]
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> PaginatedResponse<U> {
+        PaginatedResponse {
+            data: self.data.into_iter().map(f).collect(),
+            page: self.page,
+            per_page: self.per_page,
+            total: self.total,
+            links: self.links,
+        }
+    }
+
+    /// Returns the number of pages, which is at least 1.
+    #[must_use]
+    pub fn total_pages(&self) -> u64 {
+        if self.per_page == 0 {
+            return 1;
+        }
+        (self.total.saturating_add(self.per_page - 1) / self.per_page).max(1)
+    }
+
+    /// Adds `first`, `prev`, `next` and `last` links pointing at `base_url`
+    /// with the `page` and `page_size` query parameters.
+    #[must_use]
+    pub fn with_links(mut self, base_url: &str) -> Self {
+        let link = |page: u64| format!("{base_url}?page={page}&page_size={}", self.per_page);
+        let last = self.total_pages();
+        self.links = Some(PaginationLinks {
+            first: link(1),
+            prev: (self.page > 1).then(|| link(self.page.min(last + 1) - 1)),
+            next: (self.page < last).then(|| link(self.page + 1)),
+            last: link(last),
+        });
+        self
+    }
+}
+
+impl<T: Serialize> IntoResponse for PaginatedResponse<T> {
+    fn into_response(self) -> Response {
+        Json(self).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn can_serialize_paginated_response() {
+        let res = PaginatedResponse::new(vec![1, 2], 2, 2, 5).with_links("/api/notes");
+
+        assert_eq!(
+            serde_json::to_value(&res).unwrap(),
+            json!({
+                "data": [1, 2],
+                "page": 2,
+                "per_page": 2,
+                "total": 5,
+                "links": {
+                    "first": "/api/notes?page=1&page_size=2",
+                    "prev": "/api/notes?page=1&page_size=2",
+                    "next": "/api/notes?page=3&page_size=2",
+                    "last": "/api/notes?page=3&page_size=2",
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn omits_links_when_not_set() {
+        let res = PaginatedResponse::new(vec!["a"], 1, 25, 1);
+
+        assert_eq!(
+            serde_json::to_value(&res).unwrap(),
+            json!({"data": ["a"], "page": 1, "per_page": 25, "total": 1})
+        );
+        assert_eq!(res.total_pages(), 1);
+    }
+
+    #[test]
+    fn can_convert_from_page_response() {
+        let page = PageResponse {
+            page: vec![3, 4],
+            total_pages: 3,
+            total_items: 6,
+        };
+        let res = PaginatedResponse::from_page(page, &PaginationQuery::page(2));
+
+        assert_eq!(res.data, vec![3, 4]);
+        assert_eq!(res.page, 2);
+        assert_eq!(res.per_page, 25);
+        assert_eq!(res.total, 6);
+        assert_eq!(res.links, None);
+    }
+}
@@ -24,10 +24,14 @@ use std::{
     fs::File,
     io::Write,
     path::Path,
-    sync::OnceLock,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, MutexGuard, OnceLock, PoisonError,
+    },
     time::Duration,
 };
-use tracing::info;
+use tracing::{info, span, Span};
+use tracing_subscriber::{registry::LookupSpan, Registry};
 
 pub static EXTRACT_DB_NAME: OnceLock<Regex> = OnceLock::new();
 const IGNORED_TABLES: &[&str] = &[
@@ -154,7 +158,8 @@ pub async fn connect(config: &config::Database) -> Result<DbConn, sea_orm::DbErr
         opt.acquire_timeout(Duration::from_millis(acquire_timeout));
     }
 
-    let db = Database::connect(opt).await?;
+    let mut db = Database::connect(opt).await?;
+    db.set_metric_callback(count_query);
 
     match db.get_database_backend() {
         DatabaseBackend::Sqlite => {
@@ -189,6 +194,97 @@ pub async fn connect(config: &config::Database) -> Result<DbConn, sea_orm::DbErr
     Ok(db)
 }
 
+/// Queries run in each span a [`QueryCount`] is open on, by span id
+static QUERY_COUNTS: OnceLock<Mutex<HashMap<span::Id, u64>>> = OnceLock::new();
+
+/// Number of open [`QueryCount`]s, so that queries are only attributed to a
+/// span while one is open
+static OPEN_QUERY_COUNTS: AtomicUsize = AtomicUsize::new(0);
+
+fn query_counts() -> MutexGuard<'static, HashMap<span::Id, u64>> {
+    QUERY_COUNTS
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Counts the queries run on a connection from [`connect`] within the current
+/// span, and records the count as the span's `db.query_count` field when
+/// dropped. The field must be declared when the span is created.
+///
+/// Nothing is counted when the span is disabled, e.g. not sampled. Raw SQL
+/// run with `execute_unprepared` is not counted either, as `SeaORM` does not
+/// report it.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[tracing::instrument(
+///     name = "movies.list",
+///     skip_all,
+///     fields(db.query_count = tracing::field::Empty)
+/// )]
+/// pub async fn list(State(ctx): State<AppContext>) -> Result<Response> {
+///     let _queries = db::QueryCount::start();
+///     format::json(movies::Entity::find().all(&ctx.db).await?)
+/// }
+/// ```
+#[must_use = "queries are only counted while the `QueryCount` is alive"]
+pub struct QueryCount {
+    span: Span,
+}
+
+impl QueryCount {
+    /// Starts counting the queries of the current span.
+    pub fn start() -> Self {
+        let span = Span::current();
+        if let Some(id) = span.id() {
+            query_counts().insert(id, 0);
+            OPEN_QUERY_COUNTS.fetch_add(1, Ordering::Relaxed);
+        }
+        Self { span }
+    }
+}
+
+impl Drop for QueryCount {
+    fn drop(&mut self) {
+        if let Some(id) = self.span.id() {
+            let count = query_counts().remove(&id).unwrap_or_default();
+            OPEN_QUERY_COUNTS.fetch_sub(1, Ordering::Relaxed);
+            self.span.record("db.query_count", count);
+        }
+    }
+}
+
+/// Metric callback counting a query towards the current span's
+/// [`QueryCount`], if any
+fn count_query(_info: &sea_orm::metric::Info<'_>) {
+    if OPEN_QUERY_COUNTS.load(Ordering::Relaxed) == 0 {
+        return;
+    }
+    tracing::dispatcher::get_default(|dispatch| {
+        let Some(id) = dispatch.current_span().id().cloned() else {
+            return;
+        };
+        let mut counts = query_counts();
+        // queries run in spans of their own, e.g. SeaORM's, so they count
+        // towards the closest enclosing span that counts them
+        let counting = match dispatch
+            .downcast_ref::<Registry>()
+            .and_then(|registry| registry.span(&id))
+        {
+            Some(span) => span
+                .scope()
+                .map(|span| span.id())
+                .find(|id| counts.contains_key(id)),
+            None => Some(id),
+        };
+        if let Some(count) = counting.and_then(|id| counts.get_mut(&id)) {
+            *count += 1;
+        }
+    });
+}
+
 /// Extracts the database name from a given connection string.
 ///
 /// # Errors
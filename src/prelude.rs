@@ -10,7 +10,7 @@ pub use chrono::NaiveDateTime as DateTime;
 pub use include_dir::{include_dir, Dir};
 // some types required for controller generators
 #[cfg(feature = "with-db")]
-pub use sea_orm::prelude::{Date, DateTimeUtc, DateTimeWithTimeZone, Decimal, Uuid};
+pub use sea_orm::prelude::{Date, DateTimeUtc, DateTimeWithTimeZone, Decimal, Time, Uuid};
 #[cfg(feature = "with-db")]
 pub use sea_orm::{
     ActiveModelBehavior, ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait,
@@ -27,6 +27,8 @@ pub use crate::controller::extractor::{
     validate::{JsonValidate, JsonValidateWithMessage},
 };
 #[cfg(feature = "with-db")]
+pub use crate::controller::views::pagination::PaginatedResponse;
+#[cfg(feature = "with-db")]
 pub use crate::model::{query, Authenticable, ModelError, ModelResult};
 pub use crate::{
     app::{AppContext, Initializer},
@@ -718,6 +718,35 @@ pub async fn add_column(
     Ok(())
 }
 
+///
+/// Change the type of a column, keeping its data.
+///
+/// ```ignore
+/// alter_column(m, "movies", "title", ColType::Text).await;
+/// ```
+/// # Errors
+/// fails when it fails, and on `SQLite`, which cannot change a column's type
+pub async fn alter_column(
+    m: &SchemaManager<'_>,
+    table: &str,
+    name: &str,
+    atype: ColType,
+) -> Result<(), DbErr> {
+    if m.get_database_backend() == sea_orm::DatabaseBackend::Sqlite {
+        return Err(DbErr::Custom(format!(
+            "cannot change the type of `{table}.{name}`: SQLite does not support altering columns"
+        )));
+    }
+    let nz_table = normalize_table(table);
+    m.alter_table(
+        alter(Alias::new(nz_table))
+            .modify_column(atype.to_def(Alias::new(name)))
+            .to_owned(),
+    )
+    .await?;
+    Ok(())
+}
+
 ///
 /// Drop a column from a table.
 ///
@@ -0,0 +1,164 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use axum::{body::Body, http::Request, Router};
+use loco_gen::{template::BuiltinTemplateSource, AppInfo, Component, GenerateOptions};
+use loco_rs::{db, prelude::*, tests_cfg};
+use tower::ServiceExt;
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+};
+use tracing_subscriber::{
+    layer::{Context, SubscriberExt},
+    Layer,
+};
+
+/// Name and fields of a span, with the fields formatted with `Debug`
+type Span = (String, HashMap<String, String>);
+
+#[derive(Default)]
+struct Fields(HashMap<String, String>);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{value:?}"));
+    }
+}
+
+/// Layer keeping every span that is opened, with the fields recorded on it
+/// later
+#[derive(Clone, Default)]
+struct CaptureLayer(Arc<Mutex<HashMap<Id, Span>>>);
+
+impl<S: tracing::Subscriber> Layer<S> for CaptureLayer {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        attrs.record(&mut fields);
+        self.0
+            .lock()
+            .unwrap()
+            .insert(id.clone(), (attrs.metadata().name().to_string(), fields.0));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+        if let Some((_, fields)) = self.0.lock().unwrap().get_mut(id) {
+            let mut recorded = Fields::default();
+            values.record(&mut recorded);
+            fields.extend(recorded.0);
+        }
+    }
+}
+
+/// Start of the `update` action generated by
+/// `cargo loco generate scaffold movie --api --tracing`, which `update`
+/// below copies
+const GENERATED_UPDATE: &str = r#"#[tracing::instrument(
+    name = "movies.update",
+    skip_all,
+    fields(model = "movie", action = "update", db.query_count = tracing::field::Empty)
+)]
+pub async fn update(
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<Params>,
+) -> Result<Response> {
+    let _queries = loco_rs::db::QueryCount::start();
+"#;
+
+#[tracing::instrument(
+    name = "movies.update",
+    skip_all,
+    fields(model = "movie", action = "update", db.query_count = tracing::field::Empty)
+)]
+async fn update(
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<serde_json::Value>,
+) -> Result<Response> {
+    let _queries = loco_rs::db::QueryCount::start();
+    // stands in for loading and updating the item
+    let backend = ctx.db.get_database_backend();
+    ctx.db
+        .query_one(sea_orm::Statement::from_string(backend, "SELECT 1"))
+        .await?;
+    ctx.db
+        .execute(sea_orm::Statement::from_string(backend, "SELECT 2"))
+        .await?;
+    format::json((id, params))
+}
+
+#[test]
+fn generated_action_matches_scaffold() {
+    let component = Component::Scaffold {
+        name: "movie".to_string(),
+        with_tz: true,
+        fields: vec![("title".to_string(), "string".to_string())],
+        kind: loco_gen::ScaffoldKind::Api,
+        with_tracing: true,
+    };
+    let options = GenerateOptions {
+        templates: Box::new(BuiltinTemplateSource),
+        run_migration: false,
+        ..Default::default()
+    };
+
+    let files = loco_gen::preview(
+        component,
+        &AppInfo {
+            app_name: "tester".to_string(),
+        },
+        &options,
+    )
+    .expect("scaffold renders");
+    let controller = files
+        .iter()
+        .find(|file| file.path == "src/controllers/movie.rs")
+        .expect("scaffold controller");
+
+    assert!(
+        controller.content.contains(GENERATED_UPDATE),
+        "the scaffold's update action changed, update the copy in this test:\n{}",
+        controller.content
+    );
+}
+
+#[tokio::test]
+async fn generated_action_opens_span() {
+    let layer = CaptureLayer::default();
+    let _guard =
+        tracing::subscriber::set_default(tracing_subscriber::registry().with(layer.clone()));
+
+    let mut ctx = tests_cfg::app::get_app_context().await;
+    ctx.db = db::connect(&tests_cfg::config::get_database_config())
+        .await
+        .expect("database connection");
+    let app = Router::new().route("/{id}", put(update)).with_state(ctx);
+    let res = app
+        .oneshot(
+            Request::put("/42")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"title": "loco"}"#))
+                .expect("valid request"),
+        )
+        .await
+        .expect("valid response");
+    assert_eq!(res.status(), 200);
+
+    let spans = layer.0.lock().unwrap();
+    let (_, fields) = spans
+        .values()
+        .find(|(name, _)| name == "movies.update")
+        .expect("span for the action");
+    assert_eq!(
+        fields,
+        &HashMap::from([
+            ("model".to_string(), "\"movie\"".to_string()),
+            ("action".to_string(), "\"update\"".to_string()),
+            ("db.query_count".to_string(), "2".to_string()),
+        ])
+    );
+}
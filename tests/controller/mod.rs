@@ -1,3 +1,5 @@
 mod extractor;
+mod instrument;
 mod into_response;
 mod middlewares;
+mod upload;
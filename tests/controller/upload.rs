@@ -0,0 +1,169 @@
+use loco_rs::{controller::upload::UploadForm, prelude::*, tests_cfg};
+use serde::{Deserialize, Serialize};
+
+use crate::infra_cfg;
+
+const BOUNDARY: &str = "loco-boundary";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Params {
+    name: String,
+    age: Option<i32>,
+    avatar: Option<String>,
+}
+
+async fn action(State(ctx): State<AppContext>, multipart: Multipart) -> Result<Response> {
+    let params: Params = UploadForm::new("profiles")
+        .file("avatar")
+        .max_size(16)
+        .parse(&ctx, multipart)
+        .await?;
+    format::json(params)
+}
+
+/// Builds a multipart body from `(name, value)` text fields and an optional
+/// `(name, file name, content type, content)` file.
+fn body(fields: &[(&str, &str)], file: Option<(&str, &str, &str, &[u8])>) -> Vec<u8> {
+    let mut body = Vec::new();
+    for (name, value) in fields {
+        body.extend_from_slice(
+            format!(
+                "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n"
+            )
+            .as_bytes(),
+        );
+    }
+    if let Some((name, file_name, content_type, content)) = file {
+        body.extend_from_slice(
+            format!(
+                "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{name}\"; \
+                 filename=\"{file_name}\"\r\nContent-Type: {content_type}\r\n\r\n"
+            )
+            .as_bytes(),
+        );
+        body.extend_from_slice(content);
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(format!("--{BOUNDARY}--\r\n").as_bytes());
+    body
+}
+
+async fn send(port: i32, body: Vec<u8>) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(get_base_url_port(port))
+        .header(
+            "content-type",
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        )
+        .body(body)
+        .send()
+        .await
+        .expect("Valid response")
+}
+
+#[tokio::test]
+async fn can_upload_file() {
+    let ctx = tests_cfg::app::get_app_context().await;
+    let port = get_available_port().await;
+    let handle =
+        infra_cfg::server::start_with_route(ctx.clone(), "/", post(action), Some(port)).await;
+
+    let res = send(
+        port,
+        body(
+            &[("name", "loco"), ("age", "42")],
+            Some(("avatar", "../me.png", "image/png", b"png bytes")),
+        ),
+    )
+    .await;
+    assert_eq!(res.status(), 200);
+
+    let params: Params = res.json().await.expect("Valid JSON response");
+    assert_eq!(params.name, "loco");
+    assert_eq!(params.age, Some(42));
+
+    let key = params.avatar.expect("stored avatar");
+    assert!(key.starts_with("profiles/"));
+    assert!(key.ends_with("-me.png"));
+    let content: String = ctx
+        .storage
+        .download(std::path::Path::new(&key))
+        .await
+        .expect("stored file");
+    assert_eq!(content, "png bytes");
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn can_submit_without_file() {
+    let ctx = tests_cfg::app::get_app_context().await;
+    let port = get_available_port().await;
+    let handle = infra_cfg::server::start_with_route(ctx, "/", post(action), Some(port)).await;
+
+    let res = send(
+        port,
+        body(
+            &[("name", "loco")],
+            Some(("avatar", "", "application/octet-stream", b"")),
+        ),
+    )
+    .await;
+    assert_eq!(res.status(), 200);
+
+    let params: Params = res.json().await.expect("Valid JSON response");
+    assert_eq!(params.age, None);
+    assert_eq!(params.avatar, None);
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn rejects_oversized_file() {
+    let ctx = tests_cfg::app::get_app_context().await;
+    let port = get_available_port().await;
+    let handle = infra_cfg::server::start_with_route(ctx, "/", post(action), Some(port)).await;
+
+    let res = send(
+        port,
+        body(
+            &[("name", "loco")],
+            Some(("avatar", "me.png", "image/png", &[0; 17])),
+        ),
+    )
+    .await;
+    assert_eq!(res.status(), 400);
+
+    let res_json: serde_json::Value = res.json().await.expect("Valid JSON response");
+    assert_eq!(
+        res_json["description"],
+        "file exceeds the maximum size of 16 bytes"
+    );
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn rejects_disallowed_content_type() {
+    let ctx = tests_cfg::app::get_app_context().await;
+    let port = get_available_port().await;
+    let handle = infra_cfg::server::start_with_route(ctx, "/", post(action), Some(port)).await;
+
+    let res = send(
+        port,
+        body(
+            &[("name", "loco")],
+            Some(("avatar", "run.sh", "text/x-shellscript", b"echo")),
+        ),
+    )
+    .await;
+    assert_eq!(res.status(), 400);
+
+    let res_json: serde_json::Value = res.json().await.expect("Valid JSON response");
+    assert_eq!(
+        res_json["description"],
+        "content type `text/x-shellscript` is not allowed"
+    );
+
+    handle.abort();
+}